@@ -0,0 +1,362 @@
+//! Ablation Module
+//!
+//! This module measures how much each statistical test contributes to identification accuracy.
+//! For the built-in z-score tables every test is removed in turn and the corpus re-benchmarked;
+//! for trained models the values of each feature are shuffled across a held-out split
+//! (permutation importance). Accuracy changes are reported overall and per cipher type.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+use crate::benchmark::CipherTestCase;
+use crate::color::print_table;
+use crate::classifiers::Rng;
+use crate::classifiers::features::{FEATURE_NAMES, extract_features};
+use crate::crossval::{ModelKind, TrainedModel, stratified_folds};
+use crate::identify_cipher::{self, CipherScore, STAT_NAMES};
+
+/// Top-1 and top-5 accuracy over a set of samples
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Accuracy {
+    /// Number of samples evaluated
+    pub samples: usize,
+
+    /// Fraction of samples whose cipher was ranked first
+    pub top1: f64,
+
+    /// Fraction of samples whose cipher was ranked in the top five
+    pub top5: f64,
+}
+
+/// Accuracy after removing or permuting one feature
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureImpact {
+    /// Name of the feature
+    pub feature: String,
+
+    /// Overall accuracy without the feature
+    pub accuracy: Accuracy,
+
+    /// Change in top-1 accuracy compared to the baseline (negative means the feature helps)
+    pub top1_delta: f64,
+
+    /// Change in top-5 accuracy compared to the baseline (negative means the feature helps)
+    pub top5_delta: f64,
+
+    /// Accuracy without the feature for each cipher type
+    pub per_cipher: BTreeMap<String, Accuracy>,
+}
+
+/// Results of an ablation or permutation importance run
+#[derive(Debug, Clone, Serialize)]
+pub struct AblationReport {
+    /// How the features were disabled ("removal" or "permutation") and for which model
+    pub method: String,
+
+    /// Overall accuracy with all features
+    pub baseline: Accuracy,
+
+    /// Accuracy with all features for each cipher type
+    pub baseline_per_cipher: BTreeMap<String, Accuracy>,
+
+    /// Impact of each feature, in feature order
+    pub features: Vec<FeatureImpact>,
+}
+
+impl AblationReport {
+    /// Returns the change in top-1 accuracy for a feature and cipher type, if both were evaluated
+    pub fn cipher_delta(&self, feature: &FeatureImpact, cipher: &str) -> Option<f64> {
+        let baseline = self.baseline_per_cipher.get(cipher)?;
+        let ablated = feature.per_cipher.get(cipher)?;
+        Some(ablated.top1 - baseline.top1)
+    }
+}
+
+/// Re-benchmarks the built-in z-score tables with each statistical test removed
+///
+/// # Arguments
+///
+/// * `cases` - The labeled corpus
+///
+/// # Returns
+///
+/// Baseline accuracy and the accuracy without each test in [`STAT_NAMES`]
+///
+/// # Examples
+///
+/// ```no_run
+/// use cipher_identifier::ablation::ablate_tables;
+/// use cipher_identifier::benchmark::load_test_cases;
+///
+/// let cases = load_test_cases("data/random_cipher_data.json").unwrap();
+/// let report = ablate_tables(&cases).unwrap();
+/// for impact in &report.features {
+///     println!("{}: {:+.2}%", impact.feature, impact.top1_delta * 100.0);
+/// }
+/// ```
+pub fn ablate_tables(cases: &[CipherTestCase]) -> Result<AblationReport, Box<dyn Error>> {
+    if cases.is_empty() {
+        return Err("Cannot run an ablation on an empty corpus".into());
+    }
+
+    let stats: Vec<Vec<f64>> = cases.iter().map(|c| identify_cipher::get_cipher_stats(&c.ciphertext)).collect();
+    let labels: Vec<&str> = cases.iter().map(|c| c.ciphertype.as_str()).collect();
+    let cipher_types = identify_cipher::cipher_type_names();
+
+    let score = |mask: &[bool; 9]| {
+        let scores: Vec<Vec<CipherScore>> = stats
+            .iter()
+            .map(|s| identify_cipher::get_cipher_masked(s, &cipher_types, mask))
+            .collect();
+        evaluate(scores, &labels)
+    };
+
+    let (baseline, baseline_per_cipher) = score(&[true; 9]);
+    let features = STAT_NAMES
+        .iter()
+        .enumerate()
+        .map(|(j, name)| {
+            let mut mask = [true; 9];
+            mask[j] = false;
+            let (accuracy, per_cipher) = score(&mask);
+            impact(name, &baseline, accuracy, per_cipher)
+        })
+        .collect();
+
+    Ok(AblationReport { method: "removal (zscore)".to_string(), baseline, baseline_per_cipher, features })
+}
+
+/// Computes permutation importance for a model trained on part of the corpus
+///
+/// The corpus is split into stratified training (80%) and held-out (20%) parts. After
+/// training, each feature's values are shuffled across the held-out samples and the drop
+/// in accuracy is measured, averaged over `repeats` shuffles.
+///
+/// # Arguments
+///
+/// * `cases` - The labeled corpus
+/// * `kind` - The model to train and evaluate
+/// * `repeats` - Number of shuffles per feature
+/// * `seed` - Seed for the split and the shuffles
+///
+/// # Returns
+///
+/// Baseline held-out accuracy and the accuracy with each feature permuted
+pub fn permutation_importance(
+    cases: &[CipherTestCase],
+    kind: &ModelKind,
+    repeats: usize,
+    seed: u64,
+) -> Result<AblationReport, Box<dyn Error>> {
+    if cases.len() < 5 {
+        return Err("Permutation importance needs at least 5 samples".into());
+    }
+
+    let features: Vec<Vec<f64>> = cases.iter().map(|c| extract_features(&c.ciphertext)).collect();
+    let labels: Vec<String> = cases.iter().map(|c| c.ciphertype.clone()).collect();
+
+    let folds = stratified_folds(&labels, 5, seed);
+    let test_idx = &folds[0];
+    let train_idx: Vec<usize> = folds[1..].iter().flatten().copied().collect();
+
+    let train_features: Vec<Vec<f64>> = train_idx.iter().map(|&i| features[i].clone()).collect();
+    let train_labels: Vec<String> = train_idx.iter().map(|&i| labels[i].clone()).collect();
+    let model = TrainedModel::train(kind, &train_features, &train_labels)?;
+
+    let test_features: Vec<Vec<f64>> = test_idx.iter().map(|&i| features[i].clone()).collect();
+    let test_labels: Vec<&str> = test_idx.iter().map(|&i| labels[i].as_str()).collect();
+
+    let score = |features: &[Vec<f64>]| {
+        let scores: Vec<Vec<CipherScore>> = features.iter().map(|f| model.score(f)).collect();
+        evaluate(scores, &test_labels)
+    };
+
+    let (baseline, baseline_per_cipher) = score(&test_features);
+
+    // Models based on the statistics tables only look at the first STAT_NAMES features
+    let num_features = match kind {
+        ModelKind::Mlp(_) => FEATURE_NAMES.len(),
+        _ => STAT_NAMES.len(),
+    };
+
+    let mut rng = Rng::new(seed);
+    let repeats = repeats.max(1);
+    let mut impacts = Vec::with_capacity(num_features);
+
+    for (j, name) in FEATURE_NAMES.iter().enumerate().take(num_features) {
+        let mut accuracy = Accuracy::default();
+        let mut per_cipher: BTreeMap<String, Accuracy> = BTreeMap::new();
+
+        for _ in 0..repeats {
+            let mut column: Vec<f64> = test_features.iter().map(|f| f[j]).collect();
+            rng.shuffle(&mut column);
+            let permuted: Vec<Vec<f64>> = test_features
+                .iter()
+                .zip(&column)
+                .map(|(f, &v)| {
+                    let mut f = f.clone();
+                    f[j] = v;
+                    f
+                })
+                .collect();
+
+            let (acc, cipher_acc) = score(&permuted);
+            accumulate(&mut accuracy, &acc, repeats);
+            for (cipher, acc) in cipher_acc {
+                accumulate(per_cipher.entry(cipher).or_default(), &acc, repeats);
+            }
+        }
+
+        impacts.push(impact(name, &baseline, accuracy, per_cipher));
+    }
+
+    Ok(AblationReport {
+        method: format!("permutation ({})", kind.name()),
+        baseline,
+        baseline_per_cipher,
+        features: impacts,
+    })
+}
+
+/// Adds one repeat's accuracy into a running average
+fn accumulate(total: &mut Accuracy, acc: &Accuracy, repeats: usize) {
+    total.samples = acc.samples;
+    total.top1 += acc.top1 / repeats as f64;
+    total.top5 += acc.top5 / repeats as f64;
+}
+
+/// Builds the impact record for one feature
+fn impact(name: &str, baseline: &Accuracy, accuracy: Accuracy, per_cipher: BTreeMap<String, Accuracy>) -> FeatureImpact {
+    FeatureImpact {
+        feature: name.to_string(),
+        top1_delta: accuracy.top1 - baseline.top1,
+        top5_delta: accuracy.top5 - baseline.top5,
+        accuracy,
+        per_cipher,
+    }
+}
+
+/// Computes overall and per-cipher accuracy from unsorted scores
+fn evaluate(scores: Vec<Vec<CipherScore>>, labels: &[&str]) -> (Accuracy, BTreeMap<String, Accuracy>) {
+    let mut counts: BTreeMap<String, (usize, usize, usize)> = BTreeMap::new();
+    let mut total = (0, 0, 0);
+
+    for (scores, &label) in scores.into_iter().zip(labels) {
+        let rank = identify_cipher::rank_scores(scores, 5, None).iter().position(|(c, _)| c == label);
+        let entry = counts.entry(label.to_string()).or_default();
+        for count in [&mut total, entry] {
+            count.0 += 1;
+            if rank == Some(0) {
+                count.1 += 1;
+            }
+            if rank.is_some() {
+                count.2 += 1;
+            }
+        }
+    }
+
+    let to_accuracy = |(n, top1, top5): (usize, usize, usize)| Accuracy {
+        samples: n,
+        top1: top1 as f64 / n.max(1) as f64,
+        top5: top5 as f64 / n.max(1) as f64,
+    };
+
+    (to_accuracy(total), counts.into_iter().map(|(c, counts)| (c, to_accuracy(counts))).collect())
+}
+
+/// Prints an ablation report, optionally with the per-cipher breakdown
+///
+/// # Arguments
+///
+/// * `report` - The report to print
+/// * `per_cipher` - Whether to print the change in top-1 accuracy per cipher type and feature
+pub fn print_report(report: &AblationReport, per_cipher: bool) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Feature").style_spec("Fc"),
+        Cell::new("Top-1").style_spec("Fb"),
+        Cell::new("Δ Top-1").style_spec("Fb"),
+        Cell::new("Top-5").style_spec("Fb"),
+        Cell::new("Δ Top-5").style_spec("Fb"),
+    ]));
+    table.add_row(Row::new(vec![
+        Cell::new("(all features)"),
+        Cell::new(&format!("{:.2}%", report.baseline.top1 * 100.0)),
+        Cell::new(""),
+        Cell::new(&format!("{:.2}%", report.baseline.top5 * 100.0)),
+        Cell::new(""),
+    ]));
+    for impact in &report.features {
+        table.add_row(Row::new(vec![
+            Cell::new(&impact.feature),
+            Cell::new(&format!("{:.2}%", impact.accuracy.top1 * 100.0)),
+            Cell::new(&format!("{:+.2}%", impact.top1_delta * 100.0)),
+            Cell::new(&format!("{:.2}%", impact.accuracy.top5 * 100.0)),
+            Cell::new(&format!("{:+.2}%", impact.top5_delta * 100.0)),
+        ]));
+    }
+
+    println!(
+        "\n{} on {} samples (negative deltas mean the feature helps)",
+        format!("Feature ablation by {}", report.method).bold(),
+        report.baseline.samples
+    );
+    print_table(&table);
+
+    if !per_cipher {
+        return;
+    }
+
+    let mut table = Table::new();
+    let mut titles = vec![Cell::new("Cipher").style_spec("Fc"), Cell::new("Top-1").style_spec("Fb")];
+    titles.extend(report.features.iter().map(|f| Cell::new(&f.feature).style_spec("Fb")));
+    table.set_titles(Row::new(titles));
+
+    for (cipher, baseline) in &report.baseline_per_cipher {
+        let mut cells = vec![Cell::new(cipher), Cell::new(&format!("{:.0}%", baseline.top1 * 100.0))];
+        cells.extend(report.features.iter().map(|f| {
+            Cell::new(&report.cipher_delta(f, cipher).map_or(String::new(), |d| format!("{:+.0}", d * 100.0)))
+        }));
+        table.add_row(Row::new(cells));
+    }
+
+    println!("\n{}", "Change in top-1 accuracy per cipher (percentage points)".bold());
+    print_table(&table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_counts_per_cipher() {
+        let scores = vec![
+            vec![("a".to_string(), 1.0), ("b".to_string(), 2.0)],
+            vec![("a".to_string(), 1.0), ("b".to_string(), 2.0)],
+        ];
+        let (overall, per_cipher) = evaluate(scores, &["a", "b"]);
+
+        assert_eq!(overall.samples, 2);
+        assert_eq!(overall.top1, 0.5);
+        assert_eq!(overall.top5, 1.0);
+        assert_eq!(per_cipher["a"].top1, 1.0);
+        assert_eq!(per_cipher["b"].top1, 0.0);
+    }
+
+    #[test]
+    fn test_ablate_tables_reports_every_stat() {
+        let cases = vec![CipherTestCase {
+            ciphertype: "simplesubstitution".to_string(),
+            ciphertext: "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG".to_string(),
+        }];
+        let report = ablate_tables(&cases).unwrap();
+
+        assert_eq!(report.features.len(), STAT_NAMES.len());
+        assert_eq!(report.features[0].feature, "IoC");
+        assert!(report.baseline_per_cipher.contains_key("simplesubstitution"));
+    }
+}
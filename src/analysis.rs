@@ -0,0 +1,331 @@
+//! Analysis Module
+//!
+//! This module runs the `analyze` and `stats` subcommands: it reads the inputs, loads the
+//! models, priors and calibration they need, and prints each result as tables or as a report in
+//! JSON, NDJSON or CSV. With `--watch`, a file is analyzed again whenever it changes.
+
+use std::error::Error;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::thread;
+
+use colored::Colorize;
+
+use crate::calibration::{Calibration, OutOfDistribution};
+use crate::charts::ChartStyle;
+use crate::cipher_analyzer::{AnalyzeArgs, StatsArgs};
+use crate::classifiers::ensemble::{CombineMethod, Ensemble, check_weights};
+use crate::classifiers::mlp::Mlp;
+use crate::classifiers::zscore::ZScoreClassifier;
+use crate::constraints::{Violation, apply_constraints};
+use crate::display::{
+    MAX_PERIOD, display_basic_stats, display_charts, display_explanation, display_identification, display_kasiski,
+    display_periods, display_rank_changes, display_ranking, display_rejection, display_statistics, display_violations,
+    display_z_scores, ranking_title,
+};
+use crate::hierarchy::identify_hierarchical;
+use crate::identify_cipher;
+use crate::input::{STDIN_PATH, read_inputs};
+use crate::models::catalog::{CandidateFilter, CipherCatalog};
+use crate::priors::Priors;
+use crate::report::{AnalysisReport, OutputFormat, StatsReport};
+use crate::statistical_tests::kasiski::get_kasiski_factors;
+use crate::statistical_tests::mic::get_periodic_ics;
+use crate::watch::{FilePoller, POLL_INTERVAL, rank_changes};
+
+/// Number of results displayed when neither the command line nor a configuration file sets it
+const DEFAULT_NUMBER: usize = 5;
+
+/// The settings shared by the analyses of all inputs of one invocation
+struct Analysis<'a> {
+    catalog: &'a CipherCatalog,
+    candidates: Vec<String>,
+    priors: Priors,
+    calibration: Calibration,
+    ensemble: Option<Ensemble>,
+    number: usize,
+    highlight: Option<&'a str>,
+    explain: Option<&'a str>,
+    format: OutputFormat,
+    verbose: u8,
+    charts: Option<ChartStyle>,
+}
+
+/// Identifies the ciphers of all inputs and prints the results
+///
+/// # Arguments
+///
+/// * `args` - The analysis options
+/// * `verbose` - Verbosity level of the table output
+/// * `catalog` - Metadata of the cipher types
+///
+/// # Returns
+///
+/// Result indicating success or failure
+pub fn run_analysis(args: AnalyzeArgs, verbose: u8, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let inputs = read_inputs(args.input.text, &args.input.file, args.input.batch)?;
+    let format = args.format.unwrap_or(OutputFormat::Table);
+
+    // Load the models and metadata before printing anything
+    let mut calibration = match args.calibration {
+        Some(path) => Calibration::load(path)?,
+        None => Calibration::default(),
+    };
+    if let Some(threshold) = args.reject_threshold {
+        calibration.reject_threshold = threshold;
+        calibration.validate()?;
+    }
+    let filter = CandidateFilter { only: args.only, exclude: args.exclude, families: args.family };
+    let ensemble = match args.model {
+        Some(model_path) => {
+            // Learned weights only fit the combination method they were learned for
+            let learned = calibration.ensemble.as_ref();
+            let combine = args.combine.or(learned.map(|l| l.combine)).unwrap_or(CombineMethod::Average);
+            let weights = match args.weights {
+                Some(weights) => weights,
+                None => match learned {
+                    Some(learned) if learned.combine == combine => learned.weights.clone(),
+                    _ => vec![1.0, 1.0],
+                },
+            };
+            let &[zscore_weight, mlp_weight] = weights.as_slice() else {
+                return Err("Ensemble weights need one weight for the z-score tables and one for the network".into());
+            };
+            check_weights(&weights)?;
+            Some(
+                Ensemble::new(combine)
+                    .with(Box::new(ZScoreClassifier { temperature: calibration.temperature }), zscore_weight)
+                    .with(Box::new(Mlp::load(model_path)?), mlp_weight),
+            )
+        }
+        None => None,
+    };
+    let priors = match args.priors {
+        Some(priors) => Priors::from_preset_or_file(&priors, catalog)?,
+        None => Priors::uniform(),
+    };
+    let analysis = Analysis {
+        catalog,
+        candidates: priors.filter(filter.apply(catalog, identify_cipher::cipher_type_names())?)?,
+        priors,
+        ensemble,
+        number: args.number.unwrap_or(DEFAULT_NUMBER),
+        highlight: args.cipher.as_deref().map(|c| catalog.resolve(c)).transpose()?,
+        explain: args.explain.as_deref().map(|c| catalog.resolve(c)).transpose()?,
+        calibration,
+        format,
+        verbose,
+        charts: args.charts,
+    };
+
+    if args.watch {
+        let [input] = inputs.as_slice() else {
+            return Err("--watch needs exactly one file".into());
+        };
+        if input.source == STDIN_PATH || format != OutputFormat::Table {
+            return Err("--watch needs a file other than standard input and the table format".into());
+        }
+        return watch(Path::new(&input.source), &analysis);
+    }
+
+    let mut reports = Vec::new();
+    for (i, input) in inputs.iter().enumerate() {
+        if format == OutputFormat::Table && inputs.len() > 1 {
+            println!("\n{}", format!("== {} ==", input.source).bold());
+        }
+        let mut report = analyze(&input.text, &analysis);
+        report.source = Some(input.source.clone());
+        if format == OutputFormat::Table {
+            continue;
+        } else if format == OutputFormat::Json {
+            reports.push(report);
+        } else {
+            print_report(&report, format, i == 0)?;
+        }
+    }
+
+    // JSON reports are always printed as one array, so that the output has the same shape for any number of inputs
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    Ok(())
+}
+
+/// Analyzes one ciphertext, printing tables in the table format, and returns its report
+fn analyze(text: &str, analysis: &Analysis) -> AnalysisReport {
+    let catalog = analysis.catalog;
+    let calibration = &analysis.calibration;
+    let format = analysis.format;
+    if format == OutputFormat::Table {
+        // Display basic statistics, and the intermediate results when verbose
+        display_basic_stats(text);
+        if analysis.verbose >= 1 {
+            display_statistics(&StatsReport::new(text));
+        }
+        if analysis.verbose >= 2 {
+            display_periods(&get_periodic_ics(text, MAX_PERIOD));
+            display_kasiski(&get_kasiski_factors(text, MAX_PERIOD));
+        }
+        if let Some(style) = analysis.charts {
+            display_charts(text, style);
+        }
+    }
+
+    // Identify cipher, combining the z-score tables with a trained model if one was given
+    let mut report = if let Some(ensemble) = &analysis.ensemble {
+        // Priors and constraints are applied to each member's negative log probabilities, whose temperature is 1
+        let mut violations: Vec<Violation> = Vec::new();
+        let scores = ensemble.score_adjusted(text, |scores| {
+            let scores = scores.into_iter().filter(|(c, _)| analysis.candidates.contains(c)).collect();
+            let (scores, found) = apply_constraints(text, analysis.priors.adjust(scores, 1.0), catalog, 1.0);
+            for violation in found {
+                if !violations.contains(&violation) {
+                    violations.push(violation);
+                }
+            }
+            scores
+        });
+        let scores = identify_cipher::rank_scores(scores, analysis.number, analysis.highlight);
+
+        if format == OutputFormat::Table {
+            display_violations(&violations);
+            let ood = OutOfDistribution::new(text, &identify_cipher::get_cipher_stats(text));
+            let rejected = ood.rejected(calibration.reject_threshold);
+            if rejected {
+                display_rejection(&ood, calibration.reject_threshold);
+            }
+            display_ranking(&ranking_title(analysis.number, rejected), &scores, None, analysis.highlight, catalog);
+            if analysis.verbose >= 2 {
+                let ciphers: Vec<&str> = scores.iter().map(|(c, _)| c.as_str()).collect();
+                display_z_scores(text, &ciphers);
+            }
+        }
+        AnalysisReport::from_scores(text, &scores, violations, calibration, catalog)
+    } else {
+        let result = identify_hierarchical(
+            text,
+            &analysis.candidates,
+            &analysis.priors,
+            analysis.number,
+            analysis.highlight,
+            calibration,
+            catalog,
+        );
+        if format == OutputFormat::Table {
+            display_identification(&result, analysis.number, analysis.highlight, calibration, catalog);
+            if analysis.verbose >= 2 {
+                let ciphers: Vec<&str> = result.ciphers.candidates.iter().map(|c| c.cipher.as_str()).collect();
+                display_z_scores(text, &ciphers);
+            }
+        }
+        AnalysisReport::new(text, &result, catalog)
+    };
+
+    if let Some(cipher) = analysis.explain {
+        let stats = identify_cipher::get_cipher_stats(text);
+        if format == OutputFormat::Table {
+            display_explanation(&stats, cipher);
+        } else {
+            report.explanation = identify_cipher::explain_cipher(&stats, cipher);
+        }
+    }
+    report
+}
+
+/// Re-analyzes a file whenever its ciphertext changes, until interrupted
+fn watch(path: &Path, analysis: &Analysis) -> Result<(), Box<dyn Error>> {
+    let mut poller = FilePoller::new(path);
+    let mut first = Some(poller.read().map_err(|e| format!("Cannot read {}: {}", path.display(), e))?);
+    let mut previous: Option<Vec<String>> = None;
+    loop {
+        if let Some(text) = first.take().or_else(|| poller.poll()) {
+            // Clear the screen and move the cursor home, so that each run replaces the last
+            if std::io::stdout().is_terminal() {
+                print!("\x1B[2J\x1B[H");
+            }
+            println!("{} (press Ctrl+C to stop)", format!("Watching {}", path.display()).bold());
+            
+            let report = analyze(&text, analysis);
+            let ranking: Vec<String> = report.ciphers.iter().map(|c| c.cipher.clone()).collect();
+            if let Some(previous) = &previous {
+                display_rank_changes(&rank_changes(previous, &ranking));
+            }
+            previous = Some(ranking);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Prints an analysis report in a machine-readable format
+///
+/// # Arguments
+///
+/// * `report` - The report to print
+/// * `format` - JSON, NDJSON or CSV; the table format is printed by the `display` module instead
+/// * `header` - Whether to print the CSV header row before the report
+///
+/// # Returns
+///
+/// Result indicating success or failure
+pub fn print_report(report: &AnalysisReport, format: OutputFormat, header: bool) -> Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&[report])?),
+        OutputFormat::Ndjson => println!("{}", serde_json::to_string(report)?),
+        OutputFormat::Csv => {
+            let mut writer = prettytable::csv::Writer::from_writer(std::io::stdout());
+            if header {
+                writer.write_record(report.csv_header())?;
+            }
+            for record in report.csv_records() {
+                writer.write_record(record)?;
+            }
+            writer.flush()?;
+        }
+        OutputFormat::Table => return Err("Reports are only printed in JSON, NDJSON or CSV".into()),
+    }
+    Ok(())
+}
+
+/// Computes the statistics of all inputs and prints them
+///
+/// # Arguments
+///
+/// * `args` - The stats options
+///
+/// # Returns
+///
+/// Result indicating success or failure
+pub fn run_stats(args: StatsArgs) -> Result<(), Box<dyn Error>> {
+    let inputs = read_inputs(args.input.text, &args.input.file, args.input.batch)?;
+    let mut reports = Vec::new();
+    for (i, input) in inputs.iter().enumerate() {
+        let mut report = StatsReport::new(&input.text);
+        report.source = Some(input.source.clone());
+        match args.format.unwrap_or(OutputFormat::Table) {
+            OutputFormat::Table => {
+                if inputs.len() > 1 {
+                    println!("\n{}", format!("== {} ==", input.source).bold());
+                }
+                display_basic_stats(&input.text);
+                display_statistics(&report);
+                if let Some(style) = args.charts {
+                    display_charts(&input.text, style);
+                }
+            }
+            OutputFormat::Json => reports.push(report),
+            OutputFormat::Ndjson => println!("{}", serde_json::to_string(&report)?),
+            OutputFormat::Csv => {
+                let mut writer = prettytable::csv::Writer::from_writer(std::io::stdout());
+                if i == 0 {
+                    writer.write_record(report.csv_header())?;
+                }
+                writer.write_record(report.csv_record())?;
+                writer.flush()?;
+            }
+        }
+    }
+    if args.format == Some(OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    Ok(())
+}
@@ -10,10 +10,10 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::identify_cipher;
-use crate::statistical_tests::all_stats;
+use crate::models::catalog::CipherCatalog;
 
 /// Represents a cipher test case
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CipherTestCase {
     /// The type of cipher
     pub ciphertype: String,
@@ -22,72 +22,112 @@ pub struct CipherTestCase {
     pub ciphertext: String,
 }
 
-/// Benchmarks the accuracy of the cipher identification algorithm
+/// Loads labeled test cases from a JSON lines file
+///
+/// Each line of the file must be a JSON object with `ciphertype` and `ciphertext` fields.
+/// Blank lines are ignored.
 ///
 /// # Arguments
 ///
-/// * `data_path` - Path to the JSON file containing test data
+/// * `data_path` - Path to the JSON lines file containing test data
 ///
 /// # Returns
 ///
-/// A tuple containing the number of correct identifications and the total number of test cases
+/// The test cases in file order
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use cipher_identifier::benchmark::benchmark;
+/// use cipher_identifier::benchmark::load_test_cases;
 ///
-/// let (correct, total) = benchmark("data/random_cipher_data.json").unwrap();
-/// println!("{}/{} correct ({:.2}% accuracy)", correct, total, correct as f64 / total as f64 * 100.0);
+/// let cases = load_test_cases("data/random_cipher_data.json").unwrap();
+/// println!("{} test cases", cases.len());
 /// ```
-pub fn benchmark<P: AsRef<Path>>(data_path: P) -> Result<(usize, usize), Box<dyn Error>> {
+pub fn load_test_cases<P: AsRef<Path>>(data_path: P) -> Result<Vec<CipherTestCase>, Box<dyn Error>> {
     let file = File::open(data_path)?;
     let reader = BufReader::new(file);
     
     let mut data = Vec::new();
     for line in reader.lines() {
         let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
         let test_case: CipherTestCase = serde_json::from_str(&line)?;
         data.push(test_case);
     }
     
+    Ok(data)
+}
+
+/// Replaces the cipher type of each test case with its canonical name
+///
+/// Labels may use any spelling or alias accepted by `CipherCatalog::lookup`, e.g. "vigenère"
+/// or "aristocrat".
+///
+/// # Arguments
+///
+/// * `cases` - The test cases to relabel
+/// * `catalog` - Metadata of the cipher types
+///
+/// # Returns
+///
+/// An error naming the first unknown label, with suggestions
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::benchmark::{canonicalize_labels, CipherTestCase};
+/// use cipher_identifier::models::catalog::CipherCatalog;
+///
+/// let mut cases = vec![CipherTestCase { ciphertype: "aristocrat".to_string(), ciphertext: "XYZ".to_string() }];
+/// canonicalize_labels(&mut cases, &CipherCatalog::bundled()).unwrap();
+/// assert_eq!(cases[0].ciphertype, "simplesubstitution");
+/// ```
+pub fn canonicalize_labels(cases: &mut [CipherTestCase], catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    for (i, case) in cases.iter_mut().enumerate() {
+        let canonical = catalog
+            .resolve(&case.ciphertype)
+            .map_err(|e| format!("Test case {}: {}", i + 1, e))?;
+        case.ciphertype = canonical.to_string();
+    }
+    Ok(())
+}
+
+/// Benchmarks the accuracy of the cipher identification algorithm
+///
+/// # Arguments
+///
+/// * `data_path` - Path to the JSON file containing test data
+/// * `catalog` - Metadata used to resolve the cipher labels
+///
+/// # Returns
+///
+/// A tuple containing the number of correct identifications and the total number of test
+/// cases, or an error if the file has no test cases
+///
+/// # Examples
+///
+/// ```no_run
+/// use cipher_identifier::benchmark::benchmark;
+/// use cipher_identifier::models::catalog::CipherCatalog;
+///
+/// let (correct, total) = benchmark("data/random_cipher_data.json", &CipherCatalog::bundled()).unwrap();
+/// println!("{}/{} correct ({:.2}% accuracy)", correct, total, correct as f64 / total as f64 * 100.0);
+/// ```
+pub fn benchmark<P: AsRef<Path>>(data_path: P, catalog: &CipherCatalog) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut data = load_test_cases(data_path)?;
+    if data.is_empty() {
+        return Err("Cannot run a benchmark on an empty corpus".into());
+    }
+    canonicalize_labels(&mut data, catalog)?;
+    
     let mut correct = 0;
     
-    // List of cipher types to check
-    #[rustfmt::skip]
-    let cipher_types = vec![
-        "6x6bifid".to_string(), "6x6playfair".to_string(), "Autokey".to_string(), "Bazeries".to_string(), 
-        "Beaufort".to_string(), "CONDI".to_string(), "Grandpre".to_string(), "Grandpre10x10".to_string(), 
-        "Gromark".to_string(), "NihilistSub6x6".to_string(), "Patristocrat".to_string(), "Quagmire I".to_string(), 
-        "Quagmire II".to_string(), "Quagmire III".to_string(), "Quagmire IV".to_string(), "Slidefair".to_string(), 
-        "Swagman".to_string(), "Variant".to_string(), "Vigenere".to_string(), "amsco".to_string(), 
-        "bifid".to_string(), "cadenus".to_string(), "checkerboard".to_string(), "cmBifid".to_string(), 
-        "columnar".to_string(), "compressocrat".to_string(), "digrafid".to_string(), "foursquare".to_string(), 
-        "fractionatedMorse".to_string(), "grille".to_string(), "homophonic".to_string(), "keyphrase".to_string(), 
-        "monomeDinome".to_string(), "morbit".to_string(), "myszkowski".to_string(), "nicodemus".to_string(), 
-        "nihilistSub".to_string(), "nihilistTramp".to_string(), "numberedKey".to_string(), "periodicGromark".to_string(), 
-        "phillips".to_string(), "playfair".to_string(), "pollux".to_string(), "porta".to_string(), 
-        "portax".to_string(), "progressiveKey".to_string(), "ragbaby".to_string(), "redefence".to_string(), 
-        "routeTramp".to_string(), "runningKey".to_string(), "sequenceTramp".to_string(), "seriatedPlayfair".to_string(), 
-        "simplesubstitution".to_string(), "syllabary".to_string(), "tridigital".to_string(), "trifid".to_string(), 
-        "trisquare".to_string(), "twosquare".to_string()
-    ];
+    let cipher_types = identify_cipher::cipher_type_names();
     
     for item in &data {
-        let stats = all_stats::get_all_stats(&item.ciphertext);
-        
-        // Extract the scores needed for cipher identification
-        let scores = vec![
-            stats["IoC"],
-            stats["MIC"],
-            stats["MKA"],
-            stats["DIC"],
-            stats["EDI"],
-            stats["LR"],
-            stats["ROD"],
-            stats["LDI"],
-            stats["SDD"],
-        ];
+        let scores = identify_cipher::get_cipher_stats(&item.ciphertext);
         
         let num_dev = identify_cipher::get_cipher(&scores, &cipher_types);
         
@@ -112,16 +152,18 @@ pub fn benchmark<P: AsRef<Path>>(data_path: P) -> Result<(usize, usize), Box<dyn
 /// # Arguments
 ///
 /// * `data_path` - Path to the JSON file containing test data
+/// * `catalog` - Metadata used to resolve the cipher labels
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use cipher_identifier::benchmark::run_benchmark;
+/// use cipher_identifier::models::catalog::CipherCatalog;
 ///
-/// run_benchmark("data/random_cipher_data.json");
+/// run_benchmark("data/random_cipher_data.json", &CipherCatalog::bundled());
 /// ```
-pub fn run_benchmark<P: AsRef<Path>>(data_path: P) {
-    match benchmark(data_path) {
+pub fn run_benchmark<P: AsRef<Path>>(data_path: P, catalog: &CipherCatalog) {
+    match benchmark(data_path, catalog) {
         Ok((correct, total)) => {
             println!("\n{}/{} correct", correct, total);
             println!("{:.2}% accuracy", correct as f64 / total as f64 * 100.0);
@@ -151,9 +193,18 @@ mod tests {
         writeln!(file, r#"{{"ciphertype": "simplesubstitution", "ciphertext": "ABCDEFGHIJKLMNOPQRSTUVWXYZ"}}"#).unwrap();
         
         // Run benchmark
-        let (correct, total) = benchmark(&file_path).unwrap();
+        let (_correct, total) = benchmark(&file_path, &CipherCatalog::bundled()).unwrap();
         
         // We don't care about the actual results, just that it runs without errors
         assert_eq!(total, 2);
     }
+
+    #[test]
+    fn test_benchmark_rejects_empty_corpus() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("empty.json");
+        File::create(&file_path).unwrap();
+
+        assert!(benchmark(&file_path, &CipherCatalog::bundled()).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,672 @@
+//! Calibration Module
+//!
+//! This module turns the z-scores from `identify_cipher::get_cipher` into calibrated
+//! probabilities and decides whether a text fits any supported cipher at all.
+//!
+//! Probabilities come from a softmax over `-score / temperature`, where the temperature can be
+//! fitted on a labeled corpus. The reject option uses the out-of-distribution distance of the
+//! text: the median absolute z-score of its statistics against the nearest cipher profile.
+//! The median is used rather than the mean so that one badly fitting statistic does not
+//! reject an otherwise typical ciphertext.
+//!
+//! The profiles alone do not separate plaintext or encodings such as base64 from ciphertexts,
+//! so the distance is raised by [`OOD_PENALTY`] for each [`OodSignal`] the text shows. Texts
+//! too short for their statistics to mean anything are always rejected.
+//!
+//! Random letters are not rejected: non-periodic polyalphabetic ciphers such as the
+//! progressive key, running key and autokey produce letters just as evenly spread, and their
+//! profiles are as close to random letters as to their own ciphertexts.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::benchmark::CipherTestCase;
+use crate::classifiers::ensemble::{LearnedWeights, check_weights};
+use crate::identify_cipher::{self, CIPHER_TYPES, CipherScore};
+use crate::priors::Priors;
+use crate::similarity::{self, INDISTINGUISHABLE_DISTANCE, Sibling};
+use crate::statistical_tests::utils::convert_string;
+
+/// Texts with fewer letters and digits than this are rejected whatever their statistics
+pub const MIN_TEXT_LENGTH: usize = 20;
+
+/// Added to the out-of-distribution distance for each signal, enough to exceed the default threshold
+pub const OOD_PENALTY: f64 = 2.0;
+
+/// Fraction of symbols outside the cipher alphabet above which a text is not a ciphertext
+const UNSUPPORTED_SYMBOL_FRACTION: f64 = 0.01;
+
+/// Number of digits from which their positions tell base64 text apart from 6x6 ciphers
+const BASE64_MIN_DIGITS: usize = 8;
+
+/// The most common English digraphs
+const ENGLISH_DIGRAPHS: [&str; 30] = [
+    "TH", "HE", "IN", "ER", "AN", "RE", "ND", "AT", "ON", "NT", "HA", "ES", "ST", "EN", "ED",
+    "TO", "IT", "OU", "EA", "HI", "IS", "OR", "TI", "AS", "TE", "ET", "NG", "OF", "AL", "DE",
+];
+
+/// Share of common English digraphs from which a text reads like plaintext
+const PLAINTEXT_DIGRAPH_SHARE: f64 = 0.2;
+
+/// How much more common than its letter frequencies predict the digraphs of plaintext are
+const PLAINTEXT_DIGRAPH_EXCESS: f64 = 1.5;
+
+/// Parameters for turning scores into probabilities and rejecting unfamiliar texts
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::calibration::Calibration;
+///
+/// let calibration = Calibration::default();
+/// let scores = vec![("a".to_string(), 1.0), ("b".to_string(), 2.0)];
+/// let probabilities = calibration.probabilities(&scores);
+/// assert!(probabilities[0] > probabilities[1]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calibration {
+    /// Softmax temperature applied to the summed z-scores
+    pub temperature: f64,
+
+    /// Texts whose distance from the nearest cipher profile exceeds this are rejected
+    pub reject_threshold: f64,
+
+    /// Weights of the z-score tables and a neural network in an ensemble, if learned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ensemble: Option<LearnedWeights>,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Calibration { temperature: 1.0, reject_threshold: 1.5, ensemble: None }
+    }
+}
+
+impl Calibration {
+    /// Fits the temperature and reject threshold on a labeled corpus
+    ///
+    /// The temperature minimizes the negative log likelihood of the correct cipher types.
+    /// The reject threshold is the `coverage` quantile of the corpus' out-of-distribution
+    /// distances, so that a fraction of about `1 - coverage` of genuine ciphertexts would be
+    /// rejected. Texts shorter than [`MIN_TEXT_LENGTH`] are rejected anyway and do not count.
+    ///
+    /// # Arguments
+    ///
+    /// * `cases` - Labeled ciphertexts of supported cipher types
+    /// * `coverage` - Fraction of genuine ciphertexts that should be accepted, e.g. 0.99
+    ///
+    /// # Returns
+    ///
+    /// The fitted calibration
+    pub fn fit(cases: &[CipherTestCase], coverage: f64) -> Result<Self, Box<dyn Error>> {
+        if !(0.0..=1.0).contains(&coverage) {
+            return Err("Coverage must be between 0 and 1".into());
+        }
+
+        let cipher_types = identify_cipher::cipher_type_names();
+        let mut samples = Vec::new();
+        let mut distances = Vec::new();
+
+        for case in cases {
+            let stats = identify_cipher::get_cipher_stats(&case.ciphertext);
+            let ood = OutOfDistribution::new(&case.ciphertext, &stats);
+            if !ood.signals.contains(&OodSignal::TooShort) {
+                distances.push(ood.distance);
+            }
+
+            let scores = identify_cipher::get_cipher(&stats, &cipher_types);
+            if let Some(truth) = scores.iter().position(|(c, _)| c == &case.ciphertype) {
+                samples.push((scores.iter().map(|(_, s)| *s).collect::<Vec<f64>>(), truth));
+            }
+        }
+
+        if samples.is_empty() {
+            return Err("No test case has a supported cipher type".into());
+        }
+
+        // Search a logarithmic grid from 0.01 to 1000
+        let mut best = (f64::INFINITY, 1.0);
+        for k in -40..=60 {
+            let temperature = 10f64.powf(k as f64 / 20.0);
+            let nll: f64 = samples
+                .iter()
+                .map(|(scores, truth)| -log_softmax(scores, temperature)[*truth])
+                .sum();
+            if nll < best.0 {
+                best = (nll, temperature);
+            }
+        }
+
+        if distances.is_empty() {
+            return Err(format!("Every test case is shorter than {} letters and digits", MIN_TEXT_LENGTH).into());
+        }
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let index = ((coverage * distances.len() as f64).ceil() as usize).clamp(1, distances.len()) - 1;
+
+        Ok(Calibration { temperature: best.1, reject_threshold: distances[index], ensemble: None })
+    }
+
+    /// Converts scores (lower is better) into probabilities
+    ///
+    /// # Arguments
+    ///
+    /// * `scores` - The (cipher_type, score) pairs
+    ///
+    /// # Returns
+    ///
+    /// One probability per score, in the same order, summing to 1
+    pub fn probabilities(&self, scores: &[CipherScore]) -> Vec<f64> {
+        let values: Vec<f64> = scores.iter().map(|(_, s)| *s).collect();
+        log_softmax(&values, self.temperature).into_iter().map(f64::exp).collect()
+    }
+
+    /// Saves the calibration to a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    /// Loads a calibration from a JSON file written by [`Calibration::save`]
+    ///
+    /// The temperature must be positive and the reject threshold must be a number; both must
+    /// be finite, as they end up in every probability. Ensemble weights must pass
+    /// [`check_weights`].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let file = File::open(path)?;
+        let calibration: Calibration = serde_json::from_reader(BufReader::new(file))?;
+        calibration.validate()?;
+        Ok(calibration)
+    }
+
+    /// Checks that the temperature is positive and finite, the reject threshold is finite and
+    /// the ensemble weights, if any, are valid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::calibration::Calibration;
+    ///
+    /// assert!(Calibration::default().validate().is_ok());
+    /// assert!(Calibration { temperature: 0.0, ..Calibration::default() }.validate().is_err());
+    /// assert!(Calibration { reject_threshold: f64::NAN, ..Calibration::default() }.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if !(self.temperature.is_finite() && self.temperature > 0.0) {
+            return Err(format!("Calibration temperature must be positive and finite, got {}", self.temperature).into());
+        }
+        if !self.reject_threshold.is_finite() {
+            return Err(format!("Calibration reject threshold must be finite, got {}", self.reject_threshold).into());
+        }
+        if let Some(ensemble) = &self.ensemble {
+            check_weights(&ensemble.weights)?;
+        }
+        Ok(())
+    }
+}
+
+/// A cipher type in a calibrated ranking
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedCipher {
+    /// Name of the cipher type
+    pub cipher: String,
+
+    /// Summed z-score (lower is better)
+    pub score: f64,
+
+    /// Calibrated probability that the text was produced by this cipher type
+    pub probability: f64,
+
+    /// Supported ciphers that these statistics cannot tell apart from this one
+    pub siblings: Vec<Sibling>,
+}
+
+/// The result of identification with calibrated probabilities and the reject option
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibratedIdentification {
+    /// The top candidates, best first; kept even when the text is rejected
+    pub candidates: Vec<RankedCipher>,
+
+    /// Calibrated probability of the best candidate
+    pub confidence: f64,
+
+    /// Median absolute z-score against the nearest cipher profile, plus [`OOD_PENALTY`] per signal
+    pub ood_distance: f64,
+
+    /// The cipher type whose profile is nearest to the text
+    pub nearest_profile: String,
+
+    /// Signs that the text is not a ciphertext of a supported type
+    pub ood_signals: Vec<OodSignal>,
+
+    /// Whether no supported cipher fits the text
+    pub rejected: bool,
+}
+
+/// A sign that a text is not the output of any supported cipher, whatever its profile distance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OodSignal {
+    /// Fewer than [`MIN_TEXT_LENGTH`] letters and digits
+    TooShort,
+    /// Symbols other than letters, digits and `#`, as in base64 or punctuated text
+    UnsupportedSymbols,
+    /// Letters and digits laid out like base64-encoded ASCII text
+    Base64,
+    /// Common English digraphs well above what the letter frequencies predict
+    Plaintext,
+}
+
+impl fmt::Display for OodSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OodSignal::TooShort => write!(
+                f,
+                "it has fewer than {} letters and digits, too few for its statistics to mean anything",
+                MIN_TEXT_LENGTH
+            ),
+            OodSignal::UnsupportedSymbols => write!(f, "it contains symbols that no supported cipher produces"),
+            OodSignal::Base64 => write!(f, "its letters and digits are laid out like base64"),
+            OodSignal::Plaintext => write!(f, "its digraphs are as common as in English plaintext"),
+        }
+    }
+}
+
+/// How far a text is from every supported cipher
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::calibration::{OodSignal, OutOfDistribution};
+/// use cipher_identifier::identify_cipher::get_cipher_stats;
+///
+/// let text = "Q2FsbCBtZSBJc2htYWVsLiBTb21lIHllYXJzIGFnbywgbmV2ZXIgbWluZCBob3cgbG9uZyBwcmVjaXNlbHksIGhhdmluZyBsaXR0bGUgbW9uZXkgaW4gbXkgcHVyc2Uu";
+/// let ood = OutOfDistribution::new(text, &get_cipher_stats(text));
+/// assert_eq!(ood.signals, vec![OodSignal::Base64]);
+/// assert!(ood.rejected(1.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OutOfDistribution {
+    /// The cipher type whose profile is nearest to the text
+    pub nearest_profile: String,
+
+    /// Median absolute z-score against the nearest profile, plus [`OOD_PENALTY`] per signal
+    pub distance: f64,
+
+    /// Signs that the text is not a ciphertext of a supported type
+    pub signals: Vec<OodSignal>,
+}
+
+impl OutOfDistribution {
+    /// Measures the distance of a text from the supported ciphers
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text, as preprocessed for analysis
+    /// * `stats` - Statistics of the text in `identify_cipher::STAT_NAMES` order
+    pub fn new(text: &str, stats: &[f64]) -> Self {
+        let (nearest_profile, distance) = profile_distance(stats);
+        let signals = ood_signals(text);
+        OutOfDistribution {
+            nearest_profile,
+            distance: distance + OOD_PENALTY * signals.len() as f64,
+            signals,
+        }
+    }
+
+    /// Whether no supported cipher fits the text under the given reject threshold
+    pub fn rejected(&self, reject_threshold: f64) -> bool {
+        self.signals.contains(&OodSignal::TooShort) || self.distance > reject_threshold
+    }
+}
+
+/// Looks for signs that a text is not a ciphertext of a supported type
+///
+/// # Arguments
+///
+/// * `text` - The text, as preprocessed for analysis
+///
+/// # Returns
+///
+/// The signals found, in [`OodSignal`] order
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::calibration::{ood_signals, OodSignal};
+///
+/// assert_eq!(ood_signals(""), vec![OodSignal::TooShort]);
+/// assert_eq!(ood_signals("INTHEBEGINNINGGODCREATEDTHEHEAVENANDTHEEARTH"), vec![OodSignal::Plaintext]);
+/// ```
+pub fn ood_signals(text: &str) -> Vec<OodSignal> {
+    let symbols = convert_string(text);
+    let mut signals = Vec::new();
+
+    if symbols.len() < MIN_TEXT_LENGTH {
+        signals.push(OodSignal::TooShort);
+    }
+
+    let unsupported = text.chars().count() - symbols.len();
+    if unsupported as f64 > UNSUPPORTED_SYMBOL_FRACTION * text.chars().count() as f64 {
+        signals.push(OodSignal::UnsupportedSymbols);
+    }
+
+    // Each group of four base64 characters starts with the top six bits of an ASCII byte, never a digit
+    let digits: Vec<usize> = text.chars().enumerate().filter(|(_, c)| c.is_ascii_digit()).map(|(i, _)| i).collect();
+    if digits.len() >= BASE64_MIN_DIGITS
+        && digits.len() < symbols.len()
+        && digits.iter().all(|i| i % 4 != 0)
+    {
+        signals.push(OodSignal::Base64);
+    }
+
+    let letters: Vec<usize> = symbols.iter().copied().filter(|&c| c < 26).collect();
+    if letters.len() >= 2 && letters.len() == symbols.len() {
+        let n = letters.len() as f64;
+        let mut frequencies = [0.0; 26];
+        for &letter in &letters {
+            frequencies[letter] += 1.0 / n;
+        }
+
+        // Compare common English digraphs with how often the letter frequencies alone produce them
+        let digraphs: Vec<(usize, usize)> = ENGLISH_DIGRAPHS
+            .iter()
+            .map(|d| {
+                let d = d.as_bytes();
+                ((d[0] - b'A') as usize, (d[1] - b'A') as usize)
+            })
+            .collect();
+        let common = letters.windows(2).filter(|pair| digraphs.contains(&(pair[0], pair[1]))).count() as f64;
+        let share = common / (n - 1.0);
+        let expected: f64 = digraphs.iter().map(|&(a, b)| frequencies[a] * frequencies[b]).sum();
+        if share >= PLAINTEXT_DIGRAPH_SHARE && share >= PLAINTEXT_DIGRAPH_EXCESS * expected {
+            signals.push(OodSignal::Plaintext);
+        }
+    }
+
+    signals
+}
+
+/// Finds the cipher profile nearest to the given statistics
+///
+/// # Arguments
+///
+/// * `stats` - Statistics in `identify_cipher::STAT_NAMES` order
+///
+/// # Returns
+///
+/// The nearest cipher type and the median absolute z-score of the statistics against it
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::calibration::profile_distance;
+/// use cipher_identifier::identify_cipher::get_cipher_stats;
+///
+/// let (_, typical) = profile_distance(&get_cipher_stats("THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG"));
+/// let (_, degenerate) = profile_distance(&get_cipher_stats("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+/// assert!(degenerate > typical);
+/// ```
+pub fn profile_distance(stats: &[f64]) -> (String, f64) {
+    let mut nearest = (String::new(), f64::INFINITY);
+    for cipher in CIPHER_TYPES {
+        let Some(z_scores) = identify_cipher::get_stat_z_scores(stats, cipher) else {
+            continue;
+        };
+        let mut abs: Vec<f64> = z_scores.iter().map(|z| z.abs()).collect();
+        abs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median = if abs.len() % 2 == 1 {
+            abs[abs.len() / 2]
+        } else {
+            (abs[abs.len() / 2 - 1] + abs[abs.len() / 2]) / 2.0
+        };
+        if median < nearest.1 {
+            nearest = (cipher.to_string(), median);
+        }
+    }
+    nearest
+}
+
+/// Identifies the most likely cipher types with calibrated probabilities and the reject option
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext to analyze
+/// * `num_results` - The number of top candidates to return
+/// * `highlight_cipher` - Optional cipher type to move to the top of the candidates
+/// * `calibration` - The calibration to apply
+///
+/// # Returns
+///
+/// The calibrated candidates, their out-of-distribution distance and whether the text is rejected
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::calibration::{identify_calibrated, Calibration};
+///
+/// let result = identify_calibrated("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", 3, None, &Calibration::default());
+/// assert!(result.rejected);
+/// assert_eq!(result.candidates.len(), 3);
+/// ```
+pub fn identify_calibrated(
+    text: &str,
+    num_results: usize,
+    highlight_cipher: Option<&str>,
+    calibration: &Calibration,
+) -> CalibratedIdentification {
+    identify_calibrated_among(
+        text,
+        &identify_cipher::cipher_type_names(),
+        &Priors::uniform(),
+        num_results,
+        highlight_cipher,
+        calibration,
+    )
+}
+
+/// Identifies the most likely cipher types among the given candidates
+///
+/// Probabilities are normalized over the candidates only. The out-of-distribution distance
+/// is still measured against all supported ciphers.
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext to analyze
+/// * `candidates` - The cipher types to consider
+/// * `priors` - Prior weights of the cipher types
+/// * `num_results` - The number of top candidates to return
+/// * `highlight_cipher` - Optional cipher type to move to the top of the candidates
+/// * `calibration` - The calibration to apply
+///
+/// # Returns
+///
+/// The calibrated candidates, their out-of-distribution distance and whether the text is rejected
+pub fn identify_calibrated_among(
+    text: &str,
+    candidates: &[String],
+    priors: &Priors,
+    num_results: usize,
+    highlight_cipher: Option<&str>,
+    calibration: &Calibration,
+) -> CalibratedIdentification {
+    let stats = identify_cipher::get_cipher_stats(text);
+    let scores = priors.adjust(identify_cipher::get_cipher(&stats, candidates), calibration.temperature);
+    calibrate_scores(text, &stats, scores, num_results, highlight_cipher, calibration)
+}
+
+/// Ranks precomputed scores with calibrated probabilities and the reject option
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext
+/// * `stats` - Statistics of the text in `identify_cipher::STAT_NAMES` order
+/// * `scores` - The (cipher_type, score) pairs of all candidates, lower is better
+/// * `num_results` - The number of top candidates to return
+/// * `highlight_cipher` - Optional cipher type to move to the top of the candidates
+/// * `calibration` - The calibration to apply
+///
+/// # Returns
+///
+/// The calibrated candidates, their out-of-distribution distance and whether the text is rejected
+pub fn calibrate_scores(
+    text: &str,
+    stats: &[f64],
+    scores: Vec<CipherScore>,
+    num_results: usize,
+    highlight_cipher: Option<&str>,
+    calibration: &Calibration,
+) -> CalibratedIdentification {
+    let probabilities = calibration.probabilities(&scores);
+    let confidence = probabilities.iter().cloned().fold(0.0, f64::max);
+
+    let candidates = identify_cipher::rank_scores(scores.clone(), num_results, highlight_cipher)
+        .into_iter()
+        .map(|(cipher, score)| {
+            let probability = scores
+                .iter()
+                .position(|(c, _)| *c == cipher)
+                .map_or(0.0, |i| probabilities[i]);
+            let siblings = similarity::siblings(&cipher, INDISTINGUISHABLE_DISTANCE);
+            RankedCipher { cipher, score, probability, siblings }
+        })
+        .collect();
+
+    let ood = OutOfDistribution::new(text, stats);
+
+    CalibratedIdentification {
+        candidates,
+        confidence,
+        rejected: ood.rejected(calibration.reject_threshold),
+        ood_distance: ood.distance,
+        nearest_profile: ood.nearest_profile,
+        ood_signals: ood.signals,
+    }
+}
+
+/// Computes log-softmax of `-value / temperature`
+fn log_softmax(values: &[f64], temperature: f64) -> Vec<f64> {
+    let logits: Vec<f64> = values.iter().map(|v| -v / temperature).collect();
+    let max = logits.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let log_sum = logits.iter().map(|l| (l - max).exp()).sum::<f64>().ln() + max;
+    logits.iter().map(|l| l - log_sum).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classifiers::Rng;
+    use crate::classifiers::ensemble::CombineMethod;
+    use crate::input::preprocess;
+
+    #[test]
+    fn test_probabilities_sum_to_one() {
+        let scores = vec![("a".to_string(), 10.0), ("b".to_string(), 12.0), ("c".to_string(), 30.0)];
+        let probabilities = Calibration { temperature: 2.0, reject_threshold: 3.0, ensemble: None }.probabilities(&scores);
+
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((probabilities[0] / probabilities[1] - 1f64.exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_threshold_covers_corpus() {
+        let cases: Vec<CipherTestCase> = ["THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG", "LXFOPVEFRNHRLXFOPVEFRNHRLXFOPVEFRNHR"]
+            .iter()
+            .map(|t| CipherTestCase { ciphertype: "Vigenere".to_string(), ciphertext: t.to_string() })
+            .collect();
+        let calibration = Calibration::fit(&cases, 1.0).unwrap();
+
+        for case in &cases {
+            let result = identify_calibrated(&case.ciphertext, 1, None, &calibration);
+            assert!(!result.rejected);
+        }
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_parameters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("calibration.json");
+        for json in [
+            r#"{"temperature": 0.0, "reject_threshold": 1.5}"#,
+            r#"{"temperature": -1.0, "reject_threshold": 1.5}"#,
+            r#"{"temperature": 1e999, "reject_threshold": 1.5}"#,
+            r#"{"temperature": 1.0, "reject_threshold": 1.5, "ensemble": {"combine": "average", "weights": [0.0, 0.0]}}"#,
+            r#"{"temperature": 1.0, "reject_threshold": 1.5, "ensemble": {"combine": "vote", "weights": [0.5, 0.5]}}"#,
+        ] {
+            std::fs::write(&path, json).unwrap();
+            assert!(Calibration::load(&path).is_err(), "{} was accepted", json);
+        }
+        std::fs::write(&path, r#"{"temperature": 2.0, "reject_threshold": 1.5}"#).unwrap();
+        assert_eq!(Calibration::load(&path).unwrap().temperature, 2.0);
+
+        let calibration = Calibration {
+            ensemble: Some(LearnedWeights { combine: CombineMethod::Borda, weights: vec![0.3, 0.7] }),
+            ..Calibration::default()
+        };
+        calibration.save(&path).unwrap();
+        assert_eq!(Calibration::load(&path).unwrap().ensemble, calibration.ensemble);
+    }
+
+    const ENGLISH: &str = "It is a truth universally acknowledged, that a single man in possession of a good \
+        fortune, must be in want of a wife. However little known the feelings or views of such a man may be on \
+        his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families, \
+        that he is considered the rightful property of some one or other of their daughters.";
+
+    /// Letters from the seeded generator used for training, so that the test is deterministic
+    fn random_letters(seed: u64, length: usize) -> String {
+        let mut rng = Rng::new(seed);
+        (0..length).map(|_| (b'A' + (rng.next_u64() % 26) as u8) as char).collect()
+    }
+
+    fn rejected(text: &str) -> bool {
+        let text = preprocess(text);
+        OutOfDistribution::new(&text, &identify_cipher::get_cipher_stats(&text)).rejected(Calibration::default().reject_threshold)
+    }
+
+    #[test]
+    fn test_rejects_text_unlike_any_cipher() {
+        assert!(rejected(""));
+        assert!(rejected("LXFOPVEFRNHR"));
+        assert!(rejected(ENGLISH));
+        assert!(rejected("SXQgaXMgYSB0cnV0aCB1bml2ZXJzYWxseSBhY2tub3dsZWRnZWQsIHRoYXQgYSBzaW5nbGUgbWFuIGluIHBvc3Nlc3Npb24="));
+        assert!(rejected(
+            "SXQgaXMgYSB0cnV0aCB1bml2ZXJzYWxseSBhY2tub3dsZWRnZWQsIHRoYXQgYSBzaW5nbGUgbWFuIGluIHBvc3Nlc3Npb24gb2YgYSBnb29kIGZvcnR1bmUsIG11c3QgYmUgaW4gd2FudCBvZiBhIHdpZmUu"
+        ));
+        assert!(rejected("+/jT8Qm0ZkR1YxW9vPp3dLs7HcUeA2n5GqKfIbo4rNz6Ji8lMwXyOtgDaE/V+hS1uC0BF"));
+        assert!(OOD_PENALTY > Calibration::default().reject_threshold);
+    }
+
+    #[test]
+    fn test_accepts_ciphertexts() {
+        let plaintext: Vec<u8> = preprocess(ENGLISH).bytes().filter(u8::is_ascii_alphabetic).collect();
+        let vigenere: String = plaintext
+            .iter()
+            .zip(b"LEMON".iter().cycle())
+            .map(|(p, k)| (b'A' + (p - b'A' + k - b'A') % 26) as char)
+            .collect();
+        let substitution: String = plaintext.iter().map(|p| b"QWERTYUIOPASDFGHJKLZXCVBNM"[(p - b'A') as usize] as char).collect();
+        // Keyword CIPHERS, shifted one further every seven letters; its letters are spread as evenly as random ones
+        let progressive_key: String = plaintext
+            .iter()
+            .zip(b"CIPHERS".iter().cycle())
+            .enumerate()
+            .map(|(i, (p, k))| (b'A' + (((p - b'A' + k - b'A') as usize + i / 7) % 26) as u8) as char)
+            .collect();
+
+        for ciphertext in [vigenere, substitution, progressive_key] {
+            let result = identify_calibrated(&ciphertext, 3, None, &Calibration::default());
+            assert!(result.ood_signals.is_empty());
+            assert!(!result.rejected);
+        }
+
+        // Random letters cannot be told apart from non-periodic polyalphabetic ciphers
+        for seed in 0..10 {
+            assert!(ood_signals(&random_letters(seed, 300)).is_empty());
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Charts Module
+//!
+//! This module renders terminal charts of a ciphertext: letter frequencies against English, the
+//! periodic index of coincidence and a digraph heatmap.
+//!
+//! Charts are returned as strings and colored with `colored`. Every chart also tells its
+//! series apart by glyph, so it stays readable when colors are off; the ASCII style avoids the
+//! Unicode block characters for terminals and fonts that lack them.
+
+use clap::ValueEnum;
+use colored::{Color, Colorize};
+
+use crate::statistical_tests::ldi::ENGLISH_FREQUENCIES;
+use crate::statistical_tests::utils::convert_string;
+
+/// Width of the frequency bars, in characters
+const BAR_WIDTH: usize = 40;
+
+/// Height of the periodic IoC plot, in lines
+const PLOT_HEIGHT: usize = 10;
+
+/// Which characters charts are drawn with
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ChartStyle {
+    /// Unicode block elements, with eighth-character resolution
+    #[default]
+    Unicode,
+    /// Plain ASCII
+    Ascii,
+}
+
+impl ChartStyle {
+    /// Characters for a bar filled by 0 to 8 eighths of a cell
+    fn eighths(self) -> [char; 9] {
+        match self {
+            ChartStyle::Unicode => [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'],
+            ChartStyle::Ascii => [' ', ' ', ' ', ' ', '#', '#', '#', '#', '#'],
+        }
+    }
+
+    /// Characters for a column filled by 0 to 8 eighths of a cell
+    fn column_eighths(self) -> [char; 9] {
+        match self {
+            ChartStyle::Unicode => [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+            ChartStyle::Ascii => [' ', ' ', ' ', ' ', '#', '#', '#', '#', '#'],
+        }
+    }
+
+    /// Marker of the expected English frequency
+    fn marker(self) -> char {
+        match self {
+            ChartStyle::Unicode => '┃',
+            ChartStyle::Ascii => '|',
+        }
+    }
+
+    /// Characters for increasing heatmap intensities
+    fn ramp(self) -> [char; 5] {
+        match self {
+            ChartStyle::Unicode => ['·', '░', '▒', '▓', '█'],
+            ChartStyle::Ascii => ['.', ':', '+', '*', '#'],
+        }
+    }
+}
+
+/// Renders the frequency of each letter as a bar, with the English frequency marked
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext; symbols other than letters are ignored
+/// * `style` - The characters to draw with
+///
+/// # Returns
+///
+/// One line per letter, A to Z
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::charts::{ChartStyle, frequency_chart};
+///
+/// let chart = frequency_chart("HELLOWORLD", ChartStyle::Ascii);
+/// assert_eq!(chart.lines().count(), 26);
+/// ```
+pub fn frequency_chart(text: &str, style: ChartStyle) -> String {
+    let letters: Vec<usize> = convert_string(text).into_iter().filter(|&c| c < 26).collect();
+    let mut frequencies = [0.0; 26];
+    for &letter in &letters {
+        frequencies[letter] += 1.0 / letters.len() as f64;
+    }
+    let scale = frequencies.iter().chain(&ENGLISH_FREQUENCIES).copied().fold(0.0, f64::max);
+
+    let mut lines = Vec::new();
+    for (i, (&frequency, &english)) in frequencies.iter().zip(&ENGLISH_FREQUENCIES).enumerate() {
+        let mut cells: Vec<(char, Option<Color>)> = bar(frequency / scale * BAR_WIDTH as f64, style.eighths())
+            .into_iter()
+            .map(|c| (c, Some(Color::Cyan)))
+            .collect();
+        let marker = ((english / scale * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH - 1);
+        cells[marker] = (style.marker(), Some(Color::Yellow));
+
+        lines.push(format!(
+            "{} {} {:>5.1}% (English {:.1}%)",
+            (b'A' + i as u8) as char,
+            paint(&cells),
+            frequency * 100.0,
+            english * 100.0
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Renders the periodic IoC as a column per period, highlighting the highest
+///
+/// # Arguments
+///
+/// * `ics` - The (period, IoC) pairs, as returned by `get_periodic_ics`
+/// * `style` - The characters to draw with
+///
+/// # Returns
+///
+/// The plot, with the IoC scale on the left and the periods below
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::charts::{ChartStyle, periodic_ioc_chart};
+/// use cipher_identifier::statistical_tests::mic::get_periodic_ics;
+///
+/// let chart = periodic_ioc_chart(&get_periodic_ics("LXFOPVEFRNHRLXFOPVEFRNHR", 12), ChartStyle::Unicode);
+/// assert!(chart.lines().last().unwrap().contains("12"));
+/// ```
+pub fn periodic_ioc_chart(ics: &[(usize, f64)], style: ChartStyle) -> String {
+    let best = ics.iter().map(|(_, ic)| *ic).fold(0.0, f64::max);
+    let heights: Vec<f64> = ics
+        .iter()
+        .map(|(_, ic)| if best > 0.0 { ic / best * PLOT_HEIGHT as f64 } else { 0.0 })
+        .collect();
+
+    let mut lines = Vec::new();
+    for row in (0..PLOT_HEIGHT).rev() {
+        let label = match row {
+            r if r == PLOT_HEIGHT - 1 => format!("{:.3}", best),
+            0 => format!("{:.3}", 0.0),
+            _ => String::new(),
+        };
+        let mut cells = Vec::new();
+        for ((_, ic), height) in ics.iter().zip(&heights) {
+            // Eighths of this row covered by the column
+            let filled = ((height - row as f64) * 8.0).round().clamp(0.0, 8.0) as usize;
+            let color = if *ic == best { Color::Green } else { Color::Cyan };
+            let glyph = style.column_eighths()[filled];
+            cells.extend([(' ', None), (glyph, Some(color)), (glyph, Some(color))]);
+        }
+        lines.push(format!("{:>6} │{}", label, paint(&cells)));
+    }
+
+    let axis: String = ics.iter().map(|(period, _)| format!("{:>3}", period)).collect();
+    lines.push(format!("{:>6} └{}", "", "─".repeat(axis.len())));
+    lines.push(format!("{:>6}  {}", "period", axis));
+    if style == ChartStyle::Ascii {
+        lines.iter_mut().for_each(|line| *line = line.replace('│', "|").replace('└', "+").replace('─', "-"));
+    }
+    lines.join("\n")
+}
+
+/// Renders how often each letter is followed by each other letter
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext; digraphs are counted between consecutive letters
+/// * `style` - The characters to draw with
+///
+/// # Returns
+///
+/// A grid with a row per first letter and a column per second letter, and a legend
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::charts::{ChartStyle, digraph_heatmap};
+///
+/// let heatmap = digraph_heatmap("THEQUICKBROWNFOX", ChartStyle::Ascii);
+/// assert_eq!(heatmap.lines().count(), 28);
+/// ```
+pub fn digraph_heatmap(text: &str, style: ChartStyle) -> String {
+    let letters: Vec<usize> = convert_string(text).into_iter().filter(|&c| c < 26).collect();
+    let mut counts = [[0usize; 26]; 26];
+    for pair in letters.windows(2) {
+        counts[pair[0]][pair[1]] += 1;
+    }
+    let max = counts.iter().flatten().copied().max().unwrap_or(0);
+
+    let ramp = style.ramp();
+    let colors = [Color::BrightBlack, Color::Blue, Color::Cyan, Color::Yellow, Color::Red];
+    // The strongest glyph is reserved for the most frequent digraphs
+    let level = |count: usize| (count * ramp.len()).div_ceil(max) - 1;
+    let alphabet: Vec<char> = ('A'..='Z').collect();
+
+    let mut lines = vec![format!("  {}", alphabet.iter().map(|c| format!("{} ", c)).collect::<String>())];
+    for (first, row) in counts.iter().enumerate() {
+        let mut cells = Vec::new();
+        for &count in row {
+            if count == 0 {
+                cells.extend([(' ', None), (' ', None)]);
+            } else {
+                let level = level(count);
+                cells.extend([(ramp[level], Some(colors[level])), (' ', None)]);
+            }
+        }
+        lines.push(format!("{} {}", alphabet[first], paint(&cells)));
+    }
+
+    // The range of counts drawn with each glyph
+    let mut ranges: Vec<(usize, usize, usize)> = Vec::new();
+    for count in 1..=max {
+        match ranges.last_mut() {
+            Some((l, _, high)) if *l == level(count) => *high = count,
+            _ => ranges.push((level(count), count, count)),
+        }
+    }
+    let legend: Vec<String> = ranges
+        .iter()
+        .map(|&(level, low, high)| {
+            let glyph = ramp[level].to_string().color(colors[level]);
+            if low == high { format!("{}={}", glyph, low) } else { format!("{}={}-{}", glyph, low, high) }
+        })
+        .collect();
+    lines.push(format!("Scale: {} (blank = 0)", legend.join("  ")));
+    lines.join("\n")
+}
+
+/// Draws a horizontal bar of `cells` characters, with eighth-character resolution
+fn bar(cells: f64, eighths: [char; 9]) -> Vec<char> {
+    let total = (cells * 8.0).round() as usize;
+    (0..BAR_WIDTH)
+        .map(|i| eighths[total.saturating_sub(i * 8).min(8)])
+        .collect()
+}
+
+/// Joins colored characters, coloring runs of the same color at once
+fn paint(cells: &[(char, Option<Color>)]) -> String {
+    let mut painted = String::new();
+    let mut start = 0;
+    while start < cells.len() {
+        let color = cells[start].1;
+        let end = cells[start..].iter().position(|(_, c)| *c != color).map_or(cells.len(), |n| start + n);
+        let run: String = cells[start..end].iter().map(|(c, _)| c).collect();
+        match color {
+            Some(color) => painted.push_str(&run.color(color).to_string()),
+            None => painted.push_str(&run),
+        }
+        start = end;
+    }
+    painted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequency_chart_marks_english() {
+        colored::control::set_override(false);
+        let chart = frequency_chart("EEEEAAAAT", ChartStyle::Ascii);
+        let e = chart.lines().nth(4).unwrap();
+        assert!(e.starts_with("E ####"));
+        assert!(e.contains('|'));
+        assert!(e.ends_with("44.4% (English 12.7%)"));
+    }
+
+    #[test]
+    fn test_heatmap_without_colors() {
+        colored::control::set_override(false);
+        let heatmap = digraph_heatmap("ABABABCD", ChartStyle::Ascii);
+        let row_a = heatmap.lines().nth(1).unwrap();
+        // AB occurs three times, the maximum, so it gets the strongest glyph
+        assert_eq!(&row_a[..6], "A   # ");
+        assert_eq!(heatmap.lines().last().unwrap(), "Scale: :=1  *=2  #=3 (blank = 0)");
+    }
+}
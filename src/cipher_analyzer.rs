@@ -1,19 +1,36 @@
 //! Cipher Analyzer Module
 //!
 //! This module provides the CLI interface for analyzing ciphertexts and identifying cipher types.
+//! It defines the command line, fills unset options from the configuration files and
+//! dispatches each subcommand to the module that runs it: `analysis` for `analyze` and
+//! `stats`, and `commands` for the others. The tables are rendered by the `display` module.
 
-use clap::Parser;
-use colored::Colorize;
-use prettytable::{Cell, Row, Table};
-use regex::Regex;
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use std::error::Error;
-use std::fs::File;
-use std::io::Read;
 use std::path::PathBuf;
 
+use crate::analysis::{run_analysis, run_stats};
+use crate::calibration::Calibration;
+use crate::charts::ChartStyle;
+use crate::classifiers::ensemble::CombineMethod;
+use crate::color::ColorChoice;
+use crate::commands;
+use crate::config::Config;
+use crate::crossval::ModelKind;
+use crate::display;
+use crate::hierarchy::identify_hierarchical;
 use crate::identify_cipher;
-use crate::models::cipher_type::{load_cipher_types, get_cipher_primary_type};
-use crate::statistical_tests::{binary_random, ioc, shannon_entropy};
+use crate::priors::Priors;
+use crate::report::OutputFormat;
+use crate::models::catalog::CipherCatalog;
+
+/// Name of the installed binary, used in completion scripts and the man page
+pub(crate) const BIN_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// Arguments that take cipher names, completed from the catalog
+const CIPHER_ARGS: [&str; 6] = ["cipher", "explain", "only", "exclude", "a", "b"];
 
 /// Struct representing the CipherAnalyzer which provides the CLI interface
 pub struct CipherAnalyzer;
@@ -25,26 +42,393 @@ pub struct CipherAnalyzer;
     about = "Analyzes ciphertext and identifies the most likely cipher types",
     long_about = "Cipher Analyzer will analyze your ciphertext and run advanced algorithms on it to determine the correct encryption."
 )]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct CliArgs {
+    /// Analysis options, used when no subcommand is given
+    #[command(flatten)]
+    analyze: AnalyzeArgs,
+
+    /// Verbosity level: -v shows all statistical tests, -vv also per-period IoC, Kasiski factors and z-scores
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Cipher metadata file to use instead of the bundled one (also read from CIPHER_IDENTIFIER_CATALOG)
+    #[arg(long, global = true)]
+    catalog: Option<PathBuf>,
+
+    /// Configuration file with default settings, instead of .cipher_identifier.toml and the user config
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// When to color the output; "auto" colors terminals unless NO_COLOR is set
+    #[arg(long, value_enum, global = true, default_value = "auto")]
+    color: ColorChoice,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Where to read the ciphertexts from
+#[derive(Args, Debug)]
+pub struct InputArgs {
     /// The ciphertext to analyze
     #[arg(short, long)]
-    text: Option<String>,
+    pub(crate) text: Option<String>,
 
-    /// The top n most likely ciphers to display
-    #[arg(short, long, default_value = "5")]
-    number: usize,
+    /// Input files containing ciphertext; accepts glob patterns and "-" for standard input
+    #[arg(short, long)]
+    pub(crate) file: Vec<PathBuf>,
+
+    /// Treat each line of the input as a separate ciphertext (plain text or JSON with a `ciphertext` field)
+    #[arg(long)]
+    pub(crate) batch: bool,
+}
+
+/// Arguments for the `analyze` subcommand, which is also the default
+#[derive(Args, Debug)]
+pub struct AnalyzeArgs {
+    #[command(flatten)]
+    pub(crate) input: InputArgs,
+
+    /// The top n most likely ciphers to display [default: 5]
+    #[arg(short, long)]
+    pub(crate) number: Option<usize>,
 
     /// Highlight a specific cipher in the list
     #[arg(short, long)]
-    cipher: Option<String>,
+    pub(crate) cipher: Option<String>,
 
-    /// Verbosity level
-    #[arg(short, long, action = clap::ArgAction::Count)]
-    verbose: u8,
+    /// Weights file of a trained neural network to combine with the z-score tables
+    #[arg(long)]
+    pub(crate) model: Option<PathBuf>,
+
+    /// How to combine the z-score tables with --model ("average" or "borda") [default: average]
+    #[arg(long)]
+    pub(crate) combine: Option<CombineMethod>,
+
+    /// Weights of the z-score tables and --model, e.g. "0.3,0.7" [default: learned by `calibrate --model`, else 1,1]
+    #[arg(long, value_delimiter = ',', allow_negative_numbers = true)]
+    pub(crate) weights: Option<Vec<f64>>,
+
+    /// Calibration file written by the `calibrate` subcommand
+    #[arg(long)]
+    pub(crate) calibration: Option<PathBuf>,
+
+    /// Reject texts whose median z-score against the nearest cipher profile exceeds this
+    #[arg(long)]
+    pub(crate) reject_threshold: Option<f64>,
+
+    /// Prior weights of the ciphers: a preset ("uniform", "aca" or "ctf") or a JSON file mapping names to weights
+    #[arg(long)]
+    pub(crate) priors: Option<String>,
+
+    /// Show how each statistical test contributes to the score of this cipher
+    #[arg(long)]
+    pub(crate) explain: Option<String>,
+
+    /// Only consider these ciphers (comma separated names or aliases)
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) only: Vec<String>,
+
+    /// Never consider these ciphers (comma separated names or aliases)
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) exclude: Vec<String>,
+
+    /// Only consider ciphers of these families or tables, e.g. "transposition" or "polybius square"
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) family: Vec<String>,
+
+    /// Output format [default: table]
+    #[arg(long, value_enum)]
+    pub(crate) format: Option<OutputFormat>,
+
+    /// Show letter frequency, periodic IoC and digraph charts, drawn with Unicode blocks or ASCII
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "unicode")]
+    pub(crate) charts: Option<ChartStyle>,
+
+    /// Re-analyze the file whenever it changes, showing how the ranking moved
+    #[arg(long, requires = "file", conflicts_with_all = ["text", "batch"])]
+    pub(crate) watch: bool,
+}
+
+impl AnalyzeArgs {
+    /// Fills the options not given on the command line from a configuration file
+    fn apply_config(&mut self, config: &Config) -> Result<(), Box<dyn Error>> {
+        self.number = self.number.or(config.number);
+        self.model = self.model.take().or_else(|| config.model.clone());
+        self.weights = self.weights.take().or_else(|| config.weights.clone());
+        self.calibration = self.calibration.take().or_else(|| config.calibration.clone());
+        self.reject_threshold = self.reject_threshold.or(config.reject_threshold);
+        self.priors = self.priors.take().or_else(|| config.priors.clone());
+        for (option, default) in [
+            (&mut self.only, &config.only),
+            (&mut self.exclude, &config.exclude),
+            (&mut self.family, &config.family),
+        ] {
+            if option.is_empty() {
+                *option = default.clone().unwrap_or_default();
+            }
+        }
+        if self.combine.is_none()
+            && let Some(combine) = &config.combine
+        {
+            self.combine = Some(combine.parse().map_err(|e| format!("Invalid config: {}", e))?);
+        }
+        if self.format.is_none() {
+            self.format = config_format(config)?;
+        }
+        Ok(())
+    }
+}
+
+/// Arguments for the `stats` subcommand
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    #[command(flatten)]
+    pub(crate) input: InputArgs,
+
+    /// Output format [default: table]
+    #[arg(long, value_enum)]
+    pub(crate) format: Option<OutputFormat>,
+
+    /// Show letter frequency, periodic IoC and digraph charts, drawn with Unicode blocks or ASCII
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "unicode")]
+    pub(crate) charts: Option<ChartStyle>,
+}
+
+/// Arguments for the `benchmark` subcommand
+#[derive(Args, Debug)]
+pub struct BenchmarkArgs {
+    /// Labeled JSON lines corpus with `ciphertype` and `ciphertext` fields
+    pub(crate) data: PathBuf,
+}
+
+/// Arguments for the `train` subcommand
+#[derive(Args, Debug)]
+pub struct TrainArgs {
+    /// Labeled JSON lines corpus with `ciphertype` and `ciphertext` fields
+    pub(crate) data: PathBuf,
+
+    /// Where to write the weights of the trained network
+    #[arg(short, long, default_value = "model.json")]
+    pub(crate) output: PathBuf,
+
+    /// Hidden layer sizes of the neural network, e.g. "64,64"
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) hidden_layers: Option<Vec<usize>>,
+
+    /// Learning rate of the neural network
+    #[arg(long)]
+    pub(crate) learning_rate: Option<f64>,
+
+    /// Maximum number of training epochs
+    #[arg(long)]
+    pub(crate) epochs: Option<usize>,
+
+    /// Seed for weight initialization and shuffling
+    #[arg(long, default_value = "42")]
+    pub(crate) seed: u64,
+}
+
+/// Subcommands of the cipher analyzer
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Identify the most likely ciphers of a ciphertext (the default)
+    Analyze(AnalyzeArgs),
+
+    /// Show the statistics of a ciphertext without identifying it
+    Stats(StatsArgs),
+
+    /// Measure the top-5 accuracy of the z-score tables on a labeled corpus
+    Benchmark(BenchmarkArgs),
+
+    /// Train a neural network on a labeled corpus
+    Train(TrainArgs),
+
+    /// Evaluate an identification model with stratified k-fold cross-validation
+    Crossval(CrossvalArgs),
+
+    /// Measure how much each statistical test contributes to identification accuracy
+    Ablation(AblationArgs),
+
+    /// Fit the confidence temperature and reject threshold on a labeled corpus
+    Calibrate(CalibrateArgs),
+
+    /// List the supported ciphers
+    List(ListArgs),
+
+    /// Show the metadata of a supported cipher
+    Describe(DescribeArgs),
+
+    /// Compare how well two ciphers fit a ciphertext
+    Compare(CompareArgs),
+
+    /// Analyze a ciphertext interactively
+    Repl(ReplArgs),
+
+    /// Print a shell completion script, completing cipher names from the catalog
+    Completions(CompletionsArgs),
+
+    /// Print the man page in roff format
+    Man,
+}
+
+/// Arguments for the `completions` subcommand
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    /// The shell to complete in
+    pub(crate) shell: Shell,
+}
+
+/// Arguments for the `repl` subcommand
+#[derive(Args, Debug)]
+pub struct ReplArgs {
+    /// The ciphertext to start with
+    #[arg(short, long)]
+    pub(crate) text: Option<String>,
+
+    /// Input file containing the ciphertext to start with
+    #[arg(short, long)]
+    pub(crate) file: Option<PathBuf>,
+
+    /// Calibration file written by the `calibrate` subcommand
+    #[arg(long)]
+    pub(crate) calibration: Option<PathBuf>,
+}
+
+/// Arguments for the `compare` subcommand
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// The first cipher
+    pub(crate) a: String,
+
+    /// The second cipher
+    pub(crate) b: String,
+
+    /// The ciphertext to analyze
+    #[arg(short, long)]
+    pub(crate) text: Option<String>,
 
     /// Input file containing ciphertext
     #[arg(short, long)]
-    file: Option<PathBuf>,
+    pub(crate) file: Option<PathBuf>,
+
+    /// Calibration file written by the `calibrate` subcommand
+    #[arg(long)]
+    pub(crate) calibration: Option<PathBuf>,
+}
+
+/// Arguments for the `list` subcommand
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    /// Only list ciphers of this type or subtype, e.g. "transposition" or "polygraphic"
+    #[arg(long)]
+    pub(crate) family: Option<String>,
+
+    /// Only list ciphers using this kind of table, e.g. "polybius square"
+    #[arg(long)]
+    pub(crate) table: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub(crate) format: OutputFormat,
+}
+
+/// Arguments for the `describe` subcommand
+#[derive(Args, Debug)]
+pub struct DescribeArgs {
+    /// Name of the cipher
+    pub(crate) cipher: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub(crate) format: OutputFormat,
+}
+
+/// Arguments for the `calibrate` subcommand
+#[derive(Args, Debug)]
+pub struct CalibrateArgs {
+    /// Labeled JSON lines corpus with `ciphertype` and `ciphertext` fields
+    pub(crate) data: PathBuf,
+
+    /// File to write the calibration to
+    #[arg(short, long, default_value = "calibration.json")]
+    pub(crate) output: PathBuf,
+
+    /// Fraction of the corpus that must fall within the reject threshold
+    #[arg(long, default_value = "0.99")]
+    pub(crate) coverage: f64,
+
+    /// Weights file of a trained neural network to learn ensemble weights with the z-score tables for
+    #[arg(long)]
+    pub(crate) model: Option<PathBuf>,
+
+    /// How the ensemble with --model combines its members ("average" or "borda")
+    #[arg(long, default_value = "average")]
+    pub(crate) combine: CombineMethod,
+
+    /// Grid resolution of the ensemble weight search with --model
+    #[arg(long, default_value = "0.1")]
+    pub(crate) step: f64,
+}
+
+/// Arguments for the `ablation` subcommand
+#[derive(Args, Debug)]
+pub struct AblationArgs {
+    /// Labeled JSON lines corpus with `ciphertype` and `ciphertext` fields
+    pub(crate) data: PathBuf,
+
+    /// Model to analyze: "zscore" removes each test from the built-in tables, "profile" and
+    /// "mlp" are trained on 80% of the corpus and use permutation importance on the rest
+    #[arg(short, long, default_value = "zscore")]
+    pub(crate) model: ModelKind,
+
+    /// Number of shuffles per feature for permutation importance
+    #[arg(long, default_value = "5")]
+    pub(crate) repeats: usize,
+
+    /// Seed for the train/test split and the shuffles
+    #[arg(long, default_value = "42")]
+    pub(crate) seed: u64,
+
+    /// Also print the change in accuracy for each cipher type
+    #[arg(long)]
+    pub(crate) per_cipher: bool,
+}
+
+/// Arguments for the `crossval` subcommand
+#[derive(Args, Debug)]
+pub struct CrossvalArgs {
+    /// Labeled JSON lines corpus with `ciphertype` and `ciphertext` fields
+    pub(crate) data: PathBuf,
+
+    /// Number of folds
+    #[arg(short = 'k', long, default_value = "5")]
+    pub(crate) folds: usize,
+
+    /// Model to evaluate ("zscore", "profile" or "mlp")
+    #[arg(short, long, default_value = "profile")]
+    pub(crate) model: ModelKind,
+
+    /// Seed for the fold assignment and neural network training
+    #[arg(long, default_value = "42")]
+    pub(crate) seed: u64,
+
+    /// Hidden layer sizes of the neural network, comma separated
+    #[arg(long, value_delimiter = ',')]
+    pub(crate) hidden_layers: Option<Vec<usize>>,
+
+    /// Learning rate of the neural network
+    #[arg(long)]
+    pub(crate) learning_rate: Option<f64>,
+
+    /// Maximum number of training epochs of the neural network
+    #[arg(long)]
+    pub(crate) epochs: Option<usize>,
+
+    /// Search the default hyperparameter grid for the neural network instead of evaluating one model
+    #[arg(long)]
+    pub(crate) search: bool,
 }
 
 impl CipherAnalyzer {
@@ -82,28 +466,71 @@ impl CipherAnalyzer {
     /// analyzer.run(args);
     /// ```
     pub fn run(&self, args: CliArgs) -> Result<(), Box<dyn Error>> {
-        // Get text from file or command line
-        let text = if let Some(file_path) = args.file {
-            let mut file = File::open(file_path)?;
-            let mut text = String::new();
-            file.read_to_string(&mut text)?;
-            text
-        } else if let Some(text) = args.text {
-            text
-        } else {
-            return Err("Text input expected. Run with --help for usage information.".into());
-        };
+        args.color.apply();
 
-        // Preprocess text: remove whitespace and convert to uppercase
-        let text = Regex::new(r"\s+")?.replace_all(&text, "").to_string().to_uppercase();
+        // The completion script and the man page ignore the configuration files, so that a broken one cannot break them
+        let command = match args.command {
+            Some(command @ (Command::Completions(_) | Command::Man)) => {
+                return self.run_command(command, args.verbose, &CipherCatalog::load(args.catalog.as_deref())?);
+            }
+            command => command,
+        };
 
-        // Display basic statistics
-        self.display_basic_stats(&text);
+        // Options given on the command line take precedence over the configuration files
+        let config = Config::discover(args.config.as_deref(), &std::env::current_dir()?)?;
+        if let Some(Command::Stats(mut stats)) = command {
+            if stats.format.is_none() {
+                stats.format = config_format(&config)?;
+            }
+            return run_stats(stats);
+        }
+        let catalog = CipherCatalog::load(args.catalog.as_deref().or(config.catalog.as_deref()))?;
+        let verbose = if args.verbose > 0 { args.verbose } else { config.verbose.unwrap_or(0) };
 
-        // Identify cipher
-        self.identify_cipher(&text, args.number, args.cipher.as_deref())?;
+        match command {
+            Some(mut command) => {
+                match &mut command {
+                    Command::Analyze(analyze) => analyze.apply_config(&config)?,
+                    Command::Repl(repl) if repl.calibration.is_none() => repl.calibration = config.calibration.clone(),
+                    _ => {}
+                }
+                self.run_command(command, verbose, &catalog)
+            }
+            None => {
+                let mut analyze = args.analyze;
+                analyze.apply_config(&config)?;
+                run_analysis(analyze, verbose, &catalog)
+            }
+        }
+    }
 
-        Ok(())
+    /// Runs a subcommand
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The subcommand and its arguments
+    /// * `verbose` - Verbosity level of the table output
+    /// * `catalog` - Metadata of the cipher types
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or failure
+    pub fn run_command(&self, command: Command, verbose: u8, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+        match command {
+            Command::Analyze(args) => run_analysis(args, verbose, catalog),
+            Command::Stats(args) => run_stats(args),
+            Command::Benchmark(args) => commands::run_benchmark(args, catalog),
+            Command::Train(args) => commands::run_train(args, catalog),
+            Command::Crossval(args) => commands::run_crossval(args, catalog),
+            Command::Ablation(args) => commands::run_ablation(args, catalog),
+            Command::Calibrate(args) => commands::run_calibrate(args, catalog),
+            Command::List(args) => commands::run_list(args, catalog),
+            Command::Describe(args) => commands::run_describe(args, catalog),
+            Command::Compare(args) => commands::run_compare(args, catalog),
+            Command::Repl(args) => commands::run_repl(args, catalog),
+            Command::Completions(args) => commands::run_completions(args, catalog),
+            Command::Man => commands::run_man(catalog),
+        }
     }
 
     /// Displays basic statistics about the ciphertext
@@ -121,49 +548,7 @@ impl CipherAnalyzer {
     /// analyzer.display_basic_stats("HELLOWORLD");
     /// ```
     pub fn display_basic_stats(&self, text: &str) {
-        let text_length = text.len();
-        let text_ioc = ioc::get_ioc(text);
-        let text_entropy = shannon_entropy::get_shannon_entropy(text);
-        let binary_random_test = binary_random::get_binary_random(text);
-        
-        let mut table = Table::new();
-        table.set_titles(Row::new(vec![
-            Cell::new("Stat").style_spec("Fc"),
-            Cell::new("Value").style_spec("Fb"),
-        ]));
-        
-        table.add_row(Row::new(vec![
-            Cell::new("Length"),
-            Cell::new(&text_length.to_string()),
-        ]));
-        
-        table.add_row(Row::new(vec![
-            Cell::new("Number of unique characters"),
-            Cell::new(&text.chars().collect::<std::collections::HashSet<_>>().len().to_string()),
-        ]));
-        
-        table.add_row(Row::new(vec![
-            Cell::new("Missing letters"),
-            Cell::new(&self.find_missing_letters(text)),
-        ]));
-        
-        table.add_row(Row::new(vec![
-            Cell::new("IoC"),
-            Cell::new(&format!("{:.6}", text_ioc)),
-        ]));
-        
-        table.add_row(Row::new(vec![
-            Cell::new("Shannon entropy"),
-            Cell::new(&format!("{:.6}", text_entropy)),
-        ]));
-        
-        table.add_row(Row::new(vec![
-            Cell::new("Binary random test"),
-            Cell::new(&binary_random_test),
-        ]));
-        
-        println!("\n{}", "Basic stats".bold());
-        table.printstd();
+        display::display_basic_stats(text);
     }
 
     /// Identifies the most likely cipher types for the given ciphertext
@@ -187,43 +572,18 @@ impl CipherAnalyzer {
     /// analyzer.identify_cipher("HELLOWORLD", 5, None);
     /// ```
     pub fn identify_cipher(&self, text: &str, number: usize, highlight: Option<&str>) -> Result<(), Box<dyn Error>> {
-        let scores = identify_cipher::identify_cipher(text, number, highlight);
-        
-        let mut table = Table::new();
-        table.set_titles(Row::new(vec![
-            Cell::new("Cipher").style_spec("Fc"),
-            Cell::new("Score").style_spec("Fb"),
-            Cell::new("Cipher type").style_spec("Fg"),
-        ]));
-        
-        // Try to load cipher types for additional information
-        let cipher_types = load_cipher_types("resources/cipher_types.json").ok();
-        
-        for (cipher, score) in scores {
-            let cipher_type = if let Some(ref types) = cipher_types {
-                get_cipher_primary_type(types, &cipher)
-            } else {
-                "unknown".to_string()
-            };
-            
-            if Some(cipher.as_str()) == highlight {
-                table.add_row(Row::new(vec![
-                    Cell::new(&cipher).style_spec("Fm"),
-                    Cell::new(&format!("{:.3}", score)).style_spec("Fm"),
-                    Cell::new(&cipher_type).style_spec("Fm"),
-                ]));
-            } else {
-                table.add_row(Row::new(vec![
-                    Cell::new(&cipher),
-                    Cell::new(&format!("{:.3}", score)),
-                    Cell::new(&cipher_type),
-                ]));
-            }
-        }
-        
-        println!("\n{} (lower is better)", format!("Top {} most likely ciphers", number).bold());
-        table.printstd();
-        
+        let catalog = CipherCatalog::load(None)?;
+        let calibration = Calibration::default();
+        let result = identify_hierarchical(
+            text,
+            &identify_cipher::cipher_type_names(),
+            &Priors::uniform(),
+            number,
+            highlight,
+            &calibration,
+            &catalog,
+        );
+        display::display_identification(&result, number, highlight, &calibration, &catalog);
         Ok(())
     }
 
@@ -248,13 +608,13 @@ impl CipherAnalyzer {
     /// assert!(!missing.contains('L'));
     /// ```
     pub fn find_missing_letters(&self, text: &str) -> String {
-        let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
-        let text_chars: std::collections::HashSet<char> = text.chars().collect();
-        
-        alphabet
-            .chars()
-            .filter(|&c| !text_chars.contains(&c))
-            .collect()
+        display::find_missing_letters(text)
+    }
+}
+
+impl Default for CipherAnalyzer {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -271,4 +631,100 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     let args = CliArgs::parse();
     let analyzer = CipherAnalyzer::new();
     analyzer.run(args)
-}
\ No newline at end of file
+}
+
+/// Builds the command line definition used for completions and the man page
+///
+/// The arguments that take cipher names complete the names of the given catalog, so that
+/// scripts generated with `--catalog` also complete custom ciphers. Any name or alias is still
+/// accepted when parsing.
+///
+/// # Arguments
+///
+/// * `catalog` - Metadata of the cipher types whose names to complete
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::cipher_analyzer::cli_command;
+/// use cipher_identifier::models::catalog::CipherCatalog;
+///
+/// let command = cli_command(&CipherCatalog::bundled());
+/// assert_eq!(command.get_name(), "cipher_identifier");
+/// ```
+pub fn cli_command(catalog: &CipherCatalog) -> clap::Command {
+    let names: Vec<PossibleValue> = catalog.names().into_iter().map(|name| PossibleValue::new(name.to_string())).collect();
+    complete_cipher_names(CliArgs::command().name(BIN_NAME), &names)
+}
+
+/// Adds the cipher names as possible values of the cipher arguments of a command and its subcommands
+fn complete_cipher_names(command: clap::Command, names: &[PossibleValue]) -> clap::Command {
+    command
+        .mut_args(|arg| {
+            if CIPHER_ARGS.contains(&arg.get_id().as_str()) {
+                arg.value_parser(PossibleValuesParser::new(names.to_vec())).hide_possible_values(true)
+            } else {
+                arg
+            }
+        })
+        .mut_subcommands(|subcommand| complete_cipher_names(subcommand, names))
+}
+
+/// Parses the output format of a configuration file
+fn config_format(config: &Config) -> Result<Option<OutputFormat>, Box<dyn Error>> {
+    match &config.format {
+        Some(format) => Ok(Some(
+            OutputFormat::from_str(format, true).map_err(|_| format!("Invalid config: unknown format '{}'", format))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_without_subcommand_analyze() {
+        let args = CliArgs::try_parse_from(["cipher_identifier", "-t", "LXFOPVEFRNHR", "-n", "3", "--format", "csv"]).unwrap();
+        assert!(args.command.is_none());
+        assert_eq!(args.analyze.input.text.as_deref(), Some("LXFOPVEFRNHR"));
+        assert_eq!(args.analyze.number, Some(3));
+
+        let args = CliArgs::try_parse_from(["cipher_identifier", "analyze", "-t", "LXFOPVEFRNHR"]).unwrap();
+        assert!(matches!(args.command, Some(Command::Analyze(_))));
+        assert!(CliArgs::try_parse_from(["cipher_identifier", "-t", "LXFOPVEFRNHR", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_command_line_overrides_config() {
+        let config = Config {
+            number: Some(10),
+            format: Some("json".to_string()),
+            exclude: Some(vec!["playfair".to_string()]),
+            ..Config::default()
+        };
+        let mut args = CliArgs::try_parse_from(["cipher_identifier", "-t", "LXFOPVEFRNHR", "-n", "3"]).unwrap().analyze;
+        args.apply_config(&config).unwrap();
+        assert_eq!(args.number, Some(3));
+        assert_eq!(args.format, Some(OutputFormat::Json));
+        assert_eq!(args.exclude, vec!["playfair".to_string()]);
+
+        let invalid = Config { format: Some("xml".to_string()), ..Config::default() };
+        assert!(args.apply_config(&invalid).is_ok());
+        assert!(config_format(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_completions_include_cipher_names() {
+        let mut script = Vec::new();
+        clap_complete::generate(Shell::Bash, &mut cli_command(&CipherCatalog::bundled()), BIN_NAME, &mut script);
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("Vigenere"));
+        assert!(script.contains("--explain"));
+
+        // Completing the names must not restrict parsing to them
+        let args = CliArgs::try_parse_from(["cipher_identifier", "-t", "ABC", "-c", "vig"]).unwrap();
+        assert_eq!(args.analyze.cipher.as_deref(), Some("vig"));
+    }
+}
@@ -0,0 +1,418 @@
+//! Ensemble Module
+//!
+//! This module combines the outputs of several classifiers into a single ranking, either by
+//! weighted probability averaging or by weighted rank aggregation (Borda count). Member
+//! weights can be learned from a labeled validation corpus.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::benchmark::{CipherTestCase, canonicalize_labels};
+use crate::classifiers::Classifier;
+use crate::identify_cipher::{CipherScore, rank_scores};
+use crate::models::catalog::CipherCatalog;
+
+/// How the members' outputs are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CombineMethod {
+    /// Weighted mean of the members' probabilities; scores are negative log probabilities
+    Average,
+
+    /// Weighted mean of the members' rank positions; scores are the mean rank
+    Borda,
+}
+
+impl FromStr for CombineMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "average" | "avg" | "mean" => Ok(CombineMethod::Average),
+            "borda" | "rank" => Ok(CombineMethod::Borda),
+            _ => Err(format!("Unknown combine method '{}' (expected 'average' or 'borda')", s)),
+        }
+    }
+}
+
+/// Member weights learned for a combination method, as stored in a calibration file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedWeights {
+    /// Combination method the weights were learned for
+    pub combine: CombineMethod,
+
+    /// Weight of each member, in member order
+    pub weights: Vec<f64>,
+}
+
+/// Checks that member weights are non-negative and finite, and that not all of them are zero
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::classifiers::ensemble::check_weights;
+///
+/// assert!(check_weights(&[0.7, 0.3]).is_ok());
+/// assert!(check_weights(&[0.0, 0.0]).is_err());
+/// assert!(check_weights(&[1.0, -0.5]).is_err());
+/// ```
+pub fn check_weights(weights: &[f64]) -> Result<(), Box<dyn Error>> {
+    if let Some(weight) = weights.iter().find(|w| !(w.is_finite() && **w >= 0.0)) {
+        return Err(format!("Ensemble weights must be non-negative and finite, got {}", weight).into());
+    }
+    if !weights.iter().any(|w| *w > 0.0) {
+        return Err("At least one ensemble weight must be positive".into());
+    }
+    Ok(())
+}
+
+/// A weighted combination of classifiers
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::classifiers::ensemble::{CombineMethod, Ensemble};
+/// use cipher_identifier::classifiers::zscore::ZScoreClassifier;
+///
+/// let ensemble = Ensemble::new(CombineMethod::Borda)
+///     .with(Box::new(ZScoreClassifier { temperature: 1.0 }), 1.0)
+///     .with(Box::new(ZScoreClassifier { temperature: 5.0 }), 1.0);
+///
+/// let results = ensemble.identify("THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG", 5, None);
+/// assert_eq!(results.len(), 5);
+/// ```
+pub struct Ensemble {
+    members: Vec<Box<dyn Classifier>>,
+    weights: Vec<f64>,
+    method: CombineMethod,
+}
+
+impl Ensemble {
+    /// Creates an empty ensemble using the given combination method
+    pub fn new(method: CombineMethod) -> Self {
+        Ensemble { members: Vec::new(), weights: Vec::new(), method }
+    }
+
+    /// Adds a member classifier with the given weight
+    pub fn with(mut self, classifier: Box<dyn Classifier>, weight: f64) -> Self {
+        self.members.push(classifier);
+        self.weights.push(weight);
+        self
+    }
+
+    /// Returns the weight of each member, in the order they were added
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the names of the members, in the order they were added
+    pub fn member_names(&self) -> Vec<&str> {
+        self.members.iter().map(|m| m.name()).collect()
+    }
+
+    /// Returns the combination method
+    pub fn method(&self) -> CombineMethod {
+        self.method
+    }
+
+    /// Scores every cipher type known to any member
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The ciphertext to analyze
+    ///
+    /// # Returns
+    ///
+    /// An unsorted vector of (cipher_type, score) pairs, where lower scores are better
+    pub fn score(&self, text: &str) -> Vec<CipherScore> {
+        let outputs: Vec<Vec<(String, f64)>> = self.members.iter().map(|m| m.probabilities(text)).collect();
+        combine(&outputs, &self.weights, self.method)
+    }
+
+    /// Scores every cipher type known to any member, after adjusting each member's output
+    ///
+    /// Each member's probabilities are turned into negative log probabilities and passed to
+    /// `adjust`, e.g. to apply priors and constraints, which expect scores on that scale. The
+    /// adjusted scores are normalized back into probabilities before they are combined, so
+    /// this works with any combination method.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The ciphertext to analyze
+    /// * `adjust` - Maps one member's (cipher_type, negative log probability) pairs to new scores
+    ///
+    /// # Returns
+    ///
+    /// An unsorted vector of (cipher_type, score) pairs, where lower scores are better
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::classifiers::ensemble::{CombineMethod, Ensemble};
+    /// use cipher_identifier::classifiers::zscore::ZScoreClassifier;
+    ///
+    /// let ensemble = Ensemble::new(CombineMethod::Borda).with(Box::new(ZScoreClassifier { temperature: 1.0 }), 1.0);
+    /// let scores = ensemble.score_adjusted("THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG", |scores| {
+    ///     scores.into_iter().filter(|(c, _)| c == "playfair" || c == "bifid").collect()
+    /// });
+    /// assert_eq!(scores.len(), 2);
+    /// ```
+    pub fn score_adjusted<F>(&self, text: &str, mut adjust: F) -> Vec<CipherScore>
+    where
+        F: FnMut(Vec<CipherScore>) -> Vec<CipherScore>,
+    {
+        let outputs: Vec<Vec<(String, f64)>> = self
+            .members
+            .iter()
+            .map(|m| {
+                let scores = m.probabilities(text).into_iter().map(|(c, p)| (c, -p.max(1e-12).ln())).collect();
+                normalize(adjust(scores))
+            })
+            .collect();
+        combine(&outputs, &self.weights, self.method)
+    }
+
+    /// Identifies the most likely cipher types for the given text
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The ciphertext to analyze
+    /// * `num_results` - The number of top results to return
+    /// * `highlight_cipher` - Optional cipher type to move to the top of the results
+    ///
+    /// # Returns
+    ///
+    /// A vector of (cipher_type, score) pairs, sorted by score (lower is better)
+    pub fn identify(&self, text: &str, num_results: usize, highlight_cipher: Option<&str>) -> Vec<CipherScore> {
+        rank_scores(self.score(text), num_results, highlight_cipher)
+    }
+
+    /// Learns member weights that maximize top-1 accuracy on a validation corpus
+    ///
+    /// Weights are searched on a grid over the simplex with the given step size. Ties in
+    /// accuracy are broken by the mean score of the correct cipher. The learned weights
+    /// replace the current ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `cases` - Labeled validation ciphertexts
+    /// * `catalog` - Metadata used to resolve aliases in the labels, such as "aristocrat"
+    /// * `step` - Grid resolution, e.g. 0.1
+    ///
+    /// # Returns
+    ///
+    /// The learned weights, in member order
+    pub fn learn_weights(
+        &mut self,
+        cases: &[CipherTestCase],
+        catalog: &CipherCatalog,
+        step: f64,
+    ) -> Result<Vec<f64>, Box<dyn Error>> {
+        if self.members.is_empty() {
+            return Err("Ensemble has no members".into());
+        }
+        if cases.is_empty() {
+            return Err("Cannot learn weights from an empty corpus".into());
+        }
+        if !(step > 0.0 && step <= 1.0) {
+            return Err("Step must be in (0, 1]".into());
+        }
+        // The members name the ciphers by their canonical names
+        let mut cases = cases.to_vec();
+        canonicalize_labels(&mut cases, catalog)?;
+
+        // Member outputs do not depend on the weights, so compute them once
+        let outputs: Vec<Vec<Vec<(String, f64)>>> = cases
+            .iter()
+            .map(|c| self.members.iter().map(|m| m.probabilities(&c.ciphertext)).collect())
+            .collect();
+
+        let divisions = (1.0 / step).round() as usize;
+        let mut best: Option<(usize, f64, Vec<f64>)> = None;
+
+        for grid in simplex_grid(self.members.len(), divisions) {
+            let weights: Vec<f64> = grid.iter().map(|&g| g as f64 / divisions as f64).collect();
+
+            let mut correct = 0;
+            let mut truth_score = 0.0;
+            for (case, output) in cases.iter().zip(&outputs) {
+                let ranked = rank_scores(combine(output, &weights, self.method), usize::MAX, None);
+                if ranked.first().is_some_and(|(c, _)| c == &case.ciphertype) {
+                    correct += 1;
+                }
+                truth_score += ranked
+                    .iter()
+                    .find(|(c, _)| c == &case.ciphertype)
+                    .map_or(ranked.len() as f64, |(_, s)| *s);
+            }
+
+            let better = match &best {
+                None => true,
+                Some((best_correct, best_score, _)) => {
+                    correct > *best_correct || (correct == *best_correct && truth_score < *best_score)
+                }
+            };
+            if better {
+                best = Some((correct, truth_score, weights));
+            }
+        }
+
+        let (_, _, weights) = best.ok_or("No weight combination evaluated")?;
+        self.weights = weights.clone();
+        Ok(weights)
+    }
+}
+
+/// Combines member probability outputs into scores where lower is better
+fn combine(outputs: &[Vec<(String, f64)>], weights: &[f64], method: CombineMethod) -> Vec<CipherScore> {
+    let total_weight: f64 = weights.iter().sum();
+    let total_weight = if total_weight > 0.0 { total_weight } else { 1.0 };
+
+    // Union of all cipher types, in first-seen order
+    let mut ciphers: Vec<String> = Vec::new();
+    for output in outputs {
+        for (cipher, _) in output {
+            if !ciphers.contains(cipher) {
+                ciphers.push(cipher.clone());
+            }
+        }
+    }
+
+    let mut combined: HashMap<&str, f64> = ciphers.iter().map(|c| (c.as_str(), 0.0)).collect();
+
+    for (output, &weight) in outputs.iter().zip(weights) {
+        match method {
+            CombineMethod::Average => {
+                for (cipher, p) in output {
+                    *combined.get_mut(cipher.as_str()).unwrap() += weight * p / total_weight;
+                }
+            }
+            CombineMethod::Borda => {
+                let mut ranked: Vec<&(String, f64)> = output.iter().collect();
+                ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                let positions: HashMap<&str, usize> =
+                    ranked.iter().enumerate().map(|(i, (c, _))| (c.as_str(), i)).collect();
+                // Ciphers a member does not know share its last place
+                for cipher in &ciphers {
+                    let position = positions.get(cipher.as_str()).copied().unwrap_or(ranked.len());
+                    *combined.get_mut(cipher.as_str()).unwrap() += weight * position as f64 / total_weight;
+                }
+            }
+        }
+    }
+
+    ciphers
+        .iter()
+        .map(|c| {
+            let value = combined[c.as_str()];
+            let score = match method {
+                CombineMethod::Average => -value.max(1e-12).ln(),
+                CombineMethod::Borda => value,
+            };
+            (c.clone(), score)
+        })
+        .collect()
+}
+
+/// Turns negative log probabilities that may no longer be normalized into probabilities summing to 1
+fn normalize(scores: Vec<CipherScore>) -> Vec<(String, f64)> {
+    let best = scores.iter().map(|(_, s)| *s).fold(f64::INFINITY, f64::min);
+    let weights: Vec<f64> = scores.iter().map(|(_, s)| (best - s).exp()).collect();
+    let total: f64 = weights.iter().sum();
+    scores.into_iter().zip(weights).map(|((cipher, _), w)| (cipher, w / total)).collect()
+}
+
+/// Enumerates all ways to split `divisions` units among `members` weights
+fn simplex_grid(members: usize, divisions: usize) -> Vec<Vec<usize>> {
+    if members == 1 {
+        return vec![vec![divisions]];
+    }
+    let mut grid = Vec::new();
+    for first in 0..=divisions {
+        for mut rest in simplex_grid(members - 1, divisions - first) {
+            rest.insert(0, first);
+            grid.push(rest);
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(&'static str, Vec<(&'static str, f64)>);
+
+    impl Classifier for Fixed {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn probabilities(&self, _text: &str) -> Vec<(String, f64)> {
+            self.1.iter().map(|(c, p)| (c.to_string(), *p)).collect()
+        }
+    }
+
+    fn members() -> (Box<dyn Classifier>, Box<dyn Classifier>) {
+        (
+            Box::new(Fixed("a", vec![("playfair", 0.6), ("bifid", 0.4)])),
+            Box::new(Fixed("b", vec![("playfair", 0.3), ("bifid", 0.7)])),
+        )
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        let (a, b) = members();
+        let ensemble = Ensemble::new(CombineMethod::Average).with(a, 3.0).with(b, 1.0);
+        let results = ensemble.identify("", 2, None);
+
+        assert_eq!(results[0].0, "playfair");
+        assert!((results[0].1 - -(0.525f64).ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_adjusted_applies_to_each_member() {
+        let (a, b) = members();
+        let ensemble = Ensemble::new(CombineMethod::Borda).with(a, 1.0).with(b, 1.0);
+
+        // Without the penalty the members disagree; with it both rank bifid first
+        let mut seen = Vec::new();
+        let scores = ensemble.score_adjusted("", |scores| {
+            seen.extend(scores.iter().filter(|(c, _)| c == "playfair").map(|(_, s)| *s));
+            scores.into_iter().map(|(c, s)| if c == "playfair" { (c, s + 10.0) } else { (c, s) }).collect()
+        });
+        assert_eq!(seen.len(), 2);
+        assert!((seen[0] - -(0.6f64).ln()).abs() < 1e-9);
+        assert!((seen[1] - -(0.3f64).ln()).abs() < 1e-9);
+        assert_eq!(scores.iter().find(|(c, _)| c == "bifid").unwrap().1, 0.0);
+    }
+
+    #[test]
+    fn test_learn_weights_prefers_accurate_member() {
+        let (a, b) = members();
+        let mut ensemble = Ensemble::new(CombineMethod::Borda).with(a, 0.5).with(b, 0.5);
+        let cases = vec![CipherTestCase { ciphertype: "bifid".to_string(), ciphertext: String::new() }];
+        let weights = ensemble.learn_weights(&cases, &CipherCatalog::bundled(), 0.25).unwrap();
+
+        assert!(weights[1] > weights[0]);
+        assert_eq!(ensemble.identify("", 1, None)[0].0, "bifid");
+    }
+
+    #[test]
+    fn test_learn_weights_resolves_aliases() {
+        let mut ensemble = Ensemble::new(CombineMethod::Average)
+            .with(Box::new(Fixed("a", vec![("playfair", 0.6), ("simplesubstitution", 0.4)])), 0.5)
+            .with(Box::new(Fixed("b", vec![("playfair", 0.3), ("simplesubstitution", 0.7)])), 0.5);
+        let cases = vec![CipherTestCase { ciphertype: "aristocrat".to_string(), ciphertext: String::new() }];
+        let weights = ensemble.learn_weights(&cases, &CipherCatalog::bundled(), 0.25).unwrap();
+
+        assert!(weights[1] > weights[0]);
+        assert_eq!(ensemble.identify("", 1, None)[0].0, "simplesubstitution");
+
+        let unknown = vec![CipherTestCase { ciphertype: "enigma".to_string(), ciphertext: String::new() }];
+        assert!(ensemble.learn_weights(&unknown, &CipherCatalog::bundled(), 0.25).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Feature Extraction Module
+//!
+//! This module turns ciphertext into the numeric feature vectors consumed by trainable
+//! classifiers, and provides a standardizer that rescales features to zero mean and unit variance.
+
+use serde::{Deserialize, Serialize};
+
+use crate::statistical_tests::all_stats::get_all_stats;
+use crate::statistical_tests::utils::{convert_string, has_digits, has_hash};
+
+/// Names of the features returned by [`extract_features`], in order
+///
+/// The first eleven are the results of `get_all_stats`; the rest are extra
+/// features describing the symbol set and length of the ciphertext.
+pub const FEATURE_NAMES: [&str; 15] = [
+    "IoC", "MIC", "MKA", "DIC", "EDI", "LR", "ROD", "LDI", "SDD", "Shannon", "BinaryRandom",
+    "LogLength", "UniqueSymbols", "HasDigits", "HasHash",
+];
+
+/// Extracts the feature vector for the given text
+///
+/// # Arguments
+///
+/// * `text` - The input text to analyze
+///
+/// # Returns
+///
+/// The feature values in the order given by [`FEATURE_NAMES`]
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::classifiers::features::{extract_features, FEATURE_NAMES};
+///
+/// let features = extract_features("HELLOWORLD");
+/// assert_eq!(features.len(), FEATURE_NAMES.len());
+/// ```
+pub fn extract_features(text: &str) -> Vec<f64> {
+    let stats = get_all_stats(text);
+    let data = convert_string(text);
+
+    let mut features: Vec<f64> = FEATURE_NAMES[..11].iter().map(|name| stats[*name]).collect();
+
+    let mut unique = data.clone();
+    unique.sort_unstable();
+    unique.dedup();
+
+    features.push((data.len() as f64 + 1.0).ln());
+    features.push(unique.len() as f64);
+    features.push(if has_digits(&data) == "Y" { 1.0 } else { 0.0 });
+    features.push(if has_hash(&data) == "Y" { 1.0 } else { 0.0 });
+
+    features
+}
+
+/// Rescales features to zero mean and unit variance using statistics from a training set
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::classifiers::features::Standardizer;
+///
+/// let samples = vec![vec![1.0, 10.0], vec![3.0, 10.0]];
+/// let standardizer = Standardizer::fit(&samples);
+/// assert_eq!(standardizer.transform(&[3.0, 10.0]), vec![1.0, 0.0]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Standardizer {
+    /// Mean of each feature over the training set
+    pub mean: Vec<f64>,
+
+    /// Standard deviation of each feature over the training set
+    pub std: Vec<f64>,
+}
+
+impl Standardizer {
+    /// Computes the mean and standard deviation of each feature
+    ///
+    /// Features with zero variance get a standard deviation of 1 so they map to 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The training feature vectors, all of the same length
+    pub fn fit(samples: &[Vec<f64>]) -> Self {
+        let dims = samples.first().map_or(0, |s| s.len());
+        let n = samples.len().max(1) as f64;
+
+        let mut mean = vec![0.0; dims];
+        for sample in samples {
+            for (m, &x) in mean.iter_mut().zip(sample) {
+                *m += x / n;
+            }
+        }
+
+        let mut std = vec![0.0; dims];
+        for sample in samples {
+            for ((s, &x), &m) in std.iter_mut().zip(sample).zip(&mean) {
+                *s += (x - m) * (x - m) / n;
+            }
+        }
+        for s in &mut std {
+            *s = if *s > 0.0 { s.sqrt() } else { 1.0 };
+        }
+
+        Standardizer { mean, std }
+    }
+
+    /// Standardizes a single feature vector
+    pub fn transform(&self, features: &[f64]) -> Vec<f64> {
+        features
+            .iter()
+            .zip(self.mean.iter().zip(&self.std))
+            .map(|(&x, (&m, &s))| (x - m) / s)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_features_digits() {
+        let features = extract_features("12345ABCDE");
+        assert_eq!(features[13], 1.0);
+        assert_eq!(features[14], 0.0);
+        assert_eq!(features[12], 10.0);
+    }
+
+    #[test]
+    fn test_standardizer_constant_feature() {
+        let samples = vec![vec![5.0], vec![5.0], vec![5.0]];
+        let standardizer = Standardizer::fit(&samples);
+        assert_eq!(standardizer.std, vec![1.0]);
+        assert_eq!(standardizer.transform(&[5.0]), vec![0.0]);
+    }
+}
@@ -0,0 +1,660 @@
+//! Multilayer Perceptron Classifier
+//!
+//! This module implements a small feedforward neural network that runs on the CPU without
+//! any external ML runtime. It consumes standardized feature vectors from
+//! [`extract_features`](crate::classifiers::features::extract_features), is trained with
+//! mini-batch gradient descent and early stopping on a validation split, and can be saved to
+//! and loaded from a JSON weights file.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::benchmark::CipherTestCase;
+use crate::classifiers::Rng;
+use crate::classifiers::features::{FEATURE_NAMES, Standardizer, extract_features};
+use crate::identify_cipher::{CIPHER_TYPES, CipherScore};
+
+/// Hyperparameters for training an [`Mlp`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MlpConfig {
+    /// Number of neurons in each hidden layer
+    pub hidden_layers: Vec<usize>,
+
+    /// Step size for gradient descent
+    pub learning_rate: f64,
+
+    /// Momentum coefficient for gradient descent
+    pub momentum: f64,
+
+    /// L2 weight decay coefficient
+    pub l2: f64,
+
+    /// Number of samples per gradient step
+    pub batch_size: usize,
+
+    /// Maximum number of passes over the training data
+    pub max_epochs: usize,
+
+    /// Number of epochs without validation improvement before training stops
+    pub patience: usize,
+
+    /// Fraction of the samples held out for early stopping
+    pub validation_split: f64,
+
+    /// Seed for weight initialization and shuffling
+    pub seed: u64,
+}
+
+impl Default for MlpConfig {
+    fn default() -> Self {
+        MlpConfig {
+            hidden_layers: vec![64, 64],
+            learning_rate: 0.01,
+            momentum: 0.9,
+            l2: 1e-4,
+            batch_size: 32,
+            max_epochs: 200,
+            patience: 10,
+            validation_split: 0.2,
+            seed: 42,
+        }
+    }
+}
+
+impl MlpConfig {
+    /// Checks that the hyperparameters describe a network that can be trained
+    ///
+    /// Every hidden layer needs at least one neuron, the learning rate must be positive and
+    /// finite, the momentum and the validation split must lie in [0, 1), the L2 coefficient must
+    /// be non-negative and finite, and at least one epoch must be run with a patience of at least one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::classifiers::mlp::MlpConfig;
+    ///
+    /// assert!(MlpConfig::default().validate().is_ok());
+    /// assert!(MlpConfig { hidden_layers: vec![0], ..MlpConfig::default() }.validate().is_err());
+    /// assert!(MlpConfig { momentum: 1.0, ..MlpConfig::default() }.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if self.hidden_layers.contains(&0) {
+            return Err("Every hidden layer needs at least one neuron".into());
+        }
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0.0) {
+            return Err(format!("Learning rate must be positive and finite, got {}", self.learning_rate).into());
+        }
+        if !(0.0..1.0).contains(&self.momentum) {
+            return Err(format!("Momentum must be at least 0 and below 1, got {}", self.momentum).into());
+        }
+        if !(self.l2.is_finite() && self.l2 >= 0.0) {
+            return Err(format!("L2 coefficient must be non-negative and finite, got {}", self.l2).into());
+        }
+        if !(0.0..1.0).contains(&self.validation_split) {
+            return Err(format!("Validation split must be at least 0 and below 1, got {}", self.validation_split).into());
+        }
+        if self.max_epochs == 0 {
+            return Err("Training needs at least one epoch".into());
+        }
+        if self.patience == 0 {
+            return Err("Early stopping needs a patience of at least one epoch".into());
+        }
+        Ok(())
+    }
+}
+
+/// Loss and accuracy after one training epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochStats {
+    /// Epoch number, starting at 1
+    pub epoch: usize,
+
+    /// Mean cross-entropy loss on the training samples
+    pub train_loss: f64,
+
+    /// Mean cross-entropy loss on the validation samples
+    pub validation_loss: f64,
+
+    /// Top-1 accuracy on the validation samples
+    pub validation_accuracy: f64,
+}
+
+/// Summary of a training run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingReport {
+    /// Statistics for every epoch that was run
+    pub epochs: Vec<EpochStats>,
+
+    /// Epoch whose weights were kept
+    pub best_epoch: usize,
+
+    /// Whether training stopped before `max_epochs` because validation loss stopped improving
+    pub stopped_early: bool,
+}
+
+/// A fully connected layer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Layer {
+    /// Weight matrix, one row per output neuron
+    weights: Vec<Vec<f64>>,
+
+    /// Bias for each output neuron
+    biases: Vec<f64>,
+}
+
+impl Layer {
+    /// Creates a layer with He-initialized weights
+    fn new(inputs: usize, outputs: usize, rng: &mut Rng) -> Self {
+        let scale = (2.0 / inputs as f64).sqrt();
+        Layer {
+            weights: (0..outputs)
+                .map(|_| (0..inputs).map(|_| rng.next_normal() * scale).collect())
+                .collect(),
+            biases: vec![0.0; outputs],
+        }
+    }
+
+    fn forward(&self, input: &[f64]) -> Vec<f64> {
+        self.weights
+            .iter()
+            .zip(&self.biases)
+            .map(|(row, b)| row.iter().zip(input).map(|(w, x)| w * x).sum::<f64>() + b)
+            .collect()
+    }
+}
+
+/// A feedforward neural network classifier over statistical features
+///
+/// Hidden layers use ReLU activations and the output layer is a softmax over the cipher types
+/// seen during training.
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::benchmark::CipherTestCase;
+/// use cipher_identifier::classifiers::mlp::{Mlp, MlpConfig};
+///
+/// let cases = vec![
+///     CipherTestCase { ciphertype: "columnar".to_string(), ciphertext: "TEHQIUKCRBWOFNXOUJPMOSEVTRHALEZDYGO".to_string() },
+///     CipherTestCase { ciphertype: "tridigital".to_string(), ciphertext: "0123456789012345678901234567890123".to_string() },
+/// ];
+/// let config = MlpConfig { hidden_layers: vec![8], max_epochs: 20, validation_split: 0.0, ..MlpConfig::default() };
+/// let (model, _report) = Mlp::train(&cases, &config).unwrap();
+/// assert_eq!(model.rank("0918273645091827364509182736", 1)[0].0, "tridigital");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mlp {
+    /// Cipher types the network can predict, in output order
+    pub classes: Vec<String>,
+
+    /// Names of the input features, in input order
+    pub feature_names: Vec<String>,
+
+    /// Standardizer fitted on the training features
+    pub standardizer: Standardizer,
+
+    layers: Vec<Layer>,
+}
+
+impl Mlp {
+    /// Trains a network on labeled ciphertexts
+    ///
+    /// # Arguments
+    ///
+    /// * `cases` - The labeled training ciphertexts
+    /// * `config` - Training hyperparameters
+    ///
+    /// # Returns
+    ///
+    /// The trained network and a report of the training run
+    pub fn train(cases: &[CipherTestCase], config: &MlpConfig) -> Result<(Self, TrainingReport), Box<dyn Error>> {
+        config.validate()?;
+        let features: Vec<Vec<f64>> = cases.iter().map(|c| extract_features(&c.ciphertext)).collect();
+        let labels: Vec<String> = cases.iter().map(|c| c.ciphertype.clone()).collect();
+        Self::train_on_features(&features, &labels, config)
+    }
+
+    /// Trains a network on precomputed feature vectors
+    ///
+    /// This avoids recomputing the statistical tests when the same samples are used for
+    /// several training runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - Raw (unstandardized) feature vectors in [`FEATURE_NAMES`] order
+    /// * `labels` - The cipher type of each feature vector
+    /// * `config` - Training hyperparameters
+    ///
+    /// # Returns
+    ///
+    /// The trained network and a report of the training run
+    pub fn train_on_features(
+        features: &[Vec<f64>],
+        labels: &[String],
+        config: &MlpConfig,
+    ) -> Result<(Self, TrainingReport), Box<dyn Error>> {
+        config.validate()?;
+        if features.is_empty() {
+            return Err("Cannot train on an empty data set".into());
+        }
+        if features.len() != labels.len() {
+            return Err("Number of feature vectors and labels differ".into());
+        }
+        if features.iter().any(|f| f.len() != FEATURE_NAMES.len()) {
+            return Err(format!("Every feature vector must have {} features", FEATURE_NAMES.len()).into());
+        }
+
+        // Order classes like the statistics tables, with unknown labels at the end
+        let mut classes: Vec<String> = labels.to_vec();
+        classes.sort_by_key(|c| (CIPHER_TYPES.iter().position(|t| t == c).unwrap_or(usize::MAX), c.clone()));
+        classes.dedup();
+
+        let targets: Vec<usize> = labels
+            .iter()
+            .map(|l| classes.iter().position(|c| c == l).unwrap_or(0))
+            .collect();
+
+        let standardizer = Standardizer::fit(features);
+        let inputs: Vec<Vec<f64>> = features.iter().map(|f| standardizer.transform(f)).collect();
+
+        let mut rng = Rng::new(config.seed);
+        let mut order: Vec<usize> = (0..inputs.len()).collect();
+        rng.shuffle(&mut order);
+
+        let num_validation = (inputs.len() as f64 * config.validation_split).round() as usize;
+        let num_validation = num_validation.min(inputs.len() - 1);
+        let (validation_idx, train_idx) = order.split_at(num_validation);
+        let mut train_idx = train_idx.to_vec();
+        // Without a validation split, early stopping watches the training loss instead
+        let validation_idx = if validation_idx.is_empty() { train_idx.clone() } else { validation_idx.to_vec() };
+
+        let mut sizes = vec![inputs[0].len()];
+        sizes.extend(config.hidden_layers.iter().copied());
+        sizes.push(classes.len());
+
+        let mut model = Mlp {
+            classes,
+            feature_names: FEATURE_NAMES.iter().map(|s| s.to_string()).collect(),
+            standardizer,
+            layers: sizes.windows(2).map(|w| Layer::new(w[0], w[1], &mut rng)).collect(),
+        };
+
+        let mut velocity: Vec<Layer> = model
+            .layers
+            .iter()
+            .map(|l| Layer {
+                weights: vec![vec![0.0; l.weights[0].len()]; l.weights.len()],
+                biases: vec![0.0; l.biases.len()],
+            })
+            .collect();
+
+        let mut report = TrainingReport { epochs: Vec::new(), best_epoch: 0, stopped_early: false };
+        let mut best_loss = f64::INFINITY;
+        let mut best_layers = model.layers.clone();
+        let batch_size = config.batch_size.max(1);
+
+        for epoch in 1..=config.max_epochs {
+            rng.shuffle(&mut train_idx);
+
+            let mut train_loss = 0.0;
+            for batch in train_idx.chunks(batch_size) {
+                let mut grads: Vec<Layer> = velocity
+                    .iter()
+                    .map(|l| Layer {
+                        weights: vec![vec![0.0; l.weights[0].len()]; l.weights.len()],
+                        biases: vec![0.0; l.biases.len()],
+                    })
+                    .collect();
+
+                for &i in batch {
+                    train_loss += model.backward(&inputs[i], targets[i], &mut grads);
+                }
+
+                let scale = 1.0 / batch.len() as f64;
+                for ((layer, grad), vel) in model.layers.iter_mut().zip(&grads).zip(&mut velocity) {
+                    for ((w_row, g_row), v_row) in layer.weights.iter_mut().zip(&grad.weights).zip(&mut vel.weights) {
+                        for ((w, g), v) in w_row.iter_mut().zip(g_row).zip(v_row.iter_mut()) {
+                            *v = config.momentum * *v - config.learning_rate * (g * scale + config.l2 * *w);
+                            *w += *v;
+                        }
+                    }
+                    for ((b, g), v) in layer.biases.iter_mut().zip(&grad.biases).zip(&mut vel.biases) {
+                        *v = config.momentum * *v - config.learning_rate * g * scale;
+                        *b += *v;
+                    }
+                }
+            }
+
+            let (validation_loss, validation_accuracy) = model.evaluate(&inputs, &targets, &validation_idx);
+            report.epochs.push(EpochStats {
+                epoch,
+                train_loss: train_loss / train_idx.len() as f64,
+                validation_loss,
+                validation_accuracy,
+            });
+
+            if validation_loss < best_loss {
+                best_loss = validation_loss;
+                best_layers = model.layers.clone();
+                report.best_epoch = epoch;
+            } else if epoch - report.best_epoch >= config.patience {
+                report.stopped_early = true;
+                break;
+            }
+        }
+
+        model.layers = best_layers;
+        Ok((model, report))
+    }
+
+    /// Runs the network on a standardized input and returns the activations of every layer
+    fn forward(&self, input: &[f64]) -> Vec<Vec<f64>> {
+        let mut activations = vec![input.to_vec()];
+        for (i, layer) in self.layers.iter().enumerate() {
+            let mut output = layer.forward(activations.last().unwrap());
+            if i + 1 < self.layers.len() {
+                output.iter_mut().for_each(|x| *x = x.max(0.0));
+            } else {
+                softmax(&mut output);
+            }
+            activations.push(output);
+        }
+        activations
+    }
+
+    /// Accumulates the cross-entropy gradient for one sample and returns its loss
+    fn backward(&self, input: &[f64], target: usize, grads: &mut [Layer]) -> f64 {
+        let activations = self.forward(input);
+        let output = activations.last().unwrap();
+        let loss = -output[target].max(1e-12).ln();
+
+        // Gradient of the loss with respect to the softmax inputs
+        let mut delta: Vec<f64> = output.clone();
+        delta[target] -= 1.0;
+
+        for l in (0..self.layers.len()).rev() {
+            let input = &activations[l];
+            for (j, &d) in delta.iter().enumerate() {
+                grads[l].biases[j] += d;
+                for (g, &x) in grads[l].weights[j].iter_mut().zip(input) {
+                    *g += d * x;
+                }
+            }
+            if l > 0 {
+                delta = (0..input.len())
+                    .map(|k| {
+                        if input[k] <= 0.0 {
+                            0.0
+                        } else {
+                            delta.iter().zip(&self.layers[l].weights).map(|(d, row)| d * row[k]).sum()
+                        }
+                    })
+                    .collect();
+            }
+        }
+
+        loss
+    }
+
+    /// Computes mean loss and top-1 accuracy over the given sample indices
+    fn evaluate(&self, inputs: &[Vec<f64>], targets: &[usize], indices: &[usize]) -> (f64, f64) {
+        let mut loss = 0.0;
+        let mut correct = 0;
+        for &i in indices {
+            let output = self.forward(&inputs[i]).pop().unwrap();
+            loss -= output[targets[i]].max(1e-12).ln();
+            if argmax(&output) == targets[i] {
+                correct += 1;
+            }
+        }
+        let n = indices.len().max(1) as f64;
+        (loss / n, correct as f64 / n)
+    }
+
+    /// Predicts the probability of each class for a raw feature vector
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - Raw (unstandardized) features in [`FEATURE_NAMES`] order
+    ///
+    /// # Returns
+    ///
+    /// One probability per entry of `classes`
+    pub fn predict_proba(&self, features: &[f64]) -> Vec<f64> {
+        self.forward(&self.standardizer.transform(features)).pop().unwrap()
+    }
+
+    /// Ranks the cipher types for the given text
+    ///
+    /// Scores are negative log probabilities, so as with `identify_cipher` lower is better.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The ciphertext to analyze
+    /// * `num_results` - The number of top results to return
+    ///
+    /// # Returns
+    ///
+    /// A vector of (cipher_type, score) pairs, sorted by score (lower is better)
+    pub fn rank(&self, text: &str, num_results: usize) -> Vec<CipherScore> {
+        let probabilities = self.predict_proba(&extract_features(text));
+        let mut scores: Vec<CipherScore> = self
+            .classes
+            .iter()
+            .zip(probabilities)
+            .map(|(c, p)| (c.clone(), -p.max(1e-12).ln()))
+            .collect();
+        scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        scores.into_iter().take(num_results).collect()
+    }
+
+    /// Saves the network to a JSON weights file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file to write
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let file = File::create(path)?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    /// Loads a network from a JSON weights file written by [`Mlp::save`]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file to read
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let file = File::open(path)?;
+        let model: Mlp = serde_json::from_reader(BufReader::new(file))?;
+        if model.feature_names.len() != FEATURE_NAMES.len() {
+            return Err(format!(
+                "Weights file expects {} features but {} are available",
+                model.feature_names.len(),
+                FEATURE_NAMES.len()
+            )
+            .into());
+        }
+        if let Some((i, (found, expected))) =
+            model.feature_names.iter().zip(FEATURE_NAMES.iter()).enumerate().find(|(_, (found, expected))| found != *expected)
+        {
+            return Err(format!("Feature {} of the weights file is {} but {} is computed", i + 1, found, expected).into());
+        }
+        model.check_shapes()?;
+        Ok(model)
+    }
+
+    /// Checks that the standardizer and the layers fit the features and classes
+    fn check_shapes(&self) -> Result<(), Box<dyn Error>> {
+        let features = self.feature_names.len();
+        if self.standardizer.mean.len() != features || self.standardizer.std.len() != features {
+            return Err(format!("Weights file does not standardize its {} features", features).into());
+        }
+        if let Some((i, std)) = self.standardizer.std.iter().enumerate().find(|(_, s)| !(s.is_finite() && **s > 0.0)) {
+            return Err(format!(
+                "Weights file scales feature {} ({}) by a standard deviation of {}",
+                i + 1,
+                self.feature_names[i],
+                std
+            )
+            .into());
+        }
+        if self.layers.is_empty() {
+            return Err("Weights file has no layers".into());
+        }
+
+        let mut inputs = features;
+        for (i, layer) in self.layers.iter().enumerate() {
+            if layer.weights.is_empty() || layer.biases.len() != layer.weights.len() {
+                return Err(format!(
+                    "Layer {} of the weights file has {} weight rows and {} biases",
+                    i + 1,
+                    layer.weights.len(),
+                    layer.biases.len()
+                )
+                .into());
+            }
+            if let Some(row) = layer.weights.iter().find(|row| row.len() != inputs) {
+                return Err(format!(
+                    "Layer {} of the weights file takes {} inputs but {} are available",
+                    i + 1,
+                    row.len(),
+                    inputs
+                )
+                .into());
+            }
+            inputs = layer.weights.len();
+        }
+
+        if inputs != self.classes.len() {
+            return Err(format!("Weights file has {} outputs for {} classes", inputs, self.classes.len()).into());
+        }
+        Ok(())
+    }
+}
+
+/// Applies the softmax function in place
+fn softmax(values: &mut [f64]) {
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let mut sum = 0.0;
+    for v in values.iter_mut() {
+        *v = (*v - max).exp();
+        sum += *v;
+    }
+    for v in values.iter_mut() {
+        *v /= sum;
+    }
+}
+
+/// Returns the index of the largest value
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map_or(0, |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn toy_data() -> (Vec<Vec<f64>>, Vec<String>) {
+        let mut features = Vec::new();
+        let mut labels = Vec::new();
+        for i in 0..40 {
+            let x = i as f64 / 10.0;
+            let mut f = vec![0.0; FEATURE_NAMES.len()];
+            f[0] = x;
+            f[1] = 1.0 - x;
+            features.push(f);
+            labels.push(if i < 20 { "Vigenere" } else { "columnar" }.to_string());
+        }
+        (features, labels)
+    }
+
+    #[test]
+    fn test_train_separable_data() {
+        let (features, labels) = toy_data();
+        let config = MlpConfig { hidden_layers: vec![4], validation_split: 0.25, ..MlpConfig::default() };
+        let (model, report) = Mlp::train_on_features(&features, &labels, &config).unwrap();
+
+        assert_eq!(model.classes, vec!["Vigenere", "columnar"]);
+        assert!(report.best_epoch > 0);
+        let mut low = vec![0.0; FEATURE_NAMES.len()];
+        low[1] = 1.0;
+        let mut high = vec![0.0; FEATURE_NAMES.len()];
+        high[0] = 3.9;
+        high[1] = -2.9;
+        assert!(model.predict_proba(&low)[0] > 0.5);
+        assert!(model.predict_proba(&high)[1] > 0.5);
+    }
+
+    #[test]
+    fn test_train_rejects_invalid_config() {
+        let (features, labels) = toy_data();
+        for config in [
+            MlpConfig { hidden_layers: vec![0], ..MlpConfig::default() },
+            MlpConfig { hidden_layers: vec![4, 0], ..MlpConfig::default() },
+            MlpConfig { learning_rate: 0.0, ..MlpConfig::default() },
+            MlpConfig { learning_rate: f64::NAN, ..MlpConfig::default() },
+            MlpConfig { momentum: 1.0, ..MlpConfig::default() },
+            MlpConfig { momentum: -0.1, ..MlpConfig::default() },
+            MlpConfig { l2: -1e-4, ..MlpConfig::default() },
+            MlpConfig { l2: f64::INFINITY, ..MlpConfig::default() },
+            MlpConfig { validation_split: 1.0, ..MlpConfig::default() },
+            MlpConfig { validation_split: f64::NAN, ..MlpConfig::default() },
+            MlpConfig { max_epochs: 0, ..MlpConfig::default() },
+            MlpConfig { patience: 0, ..MlpConfig::default() },
+        ] {
+            assert!(Mlp::train_on_features(&features, &labels, &config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_load_rejects_mismatched_shapes() {
+        let (features, labels) = toy_data();
+        let config = MlpConfig { hidden_layers: vec![3], max_epochs: 5, ..MlpConfig::default() };
+        let (model, _) = Mlp::train_on_features(&features, &labels, &config).unwrap();
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("weights.json");
+
+        let mut extra_class = model.clone();
+        extra_class.classes.push("Vigenere".to_string());
+        let mut short_row = model.clone();
+        short_row.layers[1].weights[0].pop();
+        let mut missing_bias = model.clone();
+        missing_bias.layers[0].biases.pop();
+        let mut renamed_feature = model.clone();
+        renamed_feature.feature_names.swap(0, 1);
+        let mut constant_feature = model.clone();
+        constant_feature.standardizer.std[2] = 0.0;
+
+        for broken in [extra_class, short_row, missing_bias, renamed_feature, constant_feature] {
+            broken.save(&path).unwrap();
+            assert!(Mlp::load(&path).is_err());
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let (features, labels) = toy_data();
+        let config = MlpConfig { hidden_layers: vec![3], max_epochs: 5, ..MlpConfig::default() };
+        let (model, _) = Mlp::train_on_features(&features, &labels, &config).unwrap();
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("weights.json");
+        model.save(&path).unwrap();
+        let loaded = Mlp::load(&path).unwrap();
+
+        let input = vec![1.0; FEATURE_NAMES.len()];
+        for (a, b) in loaded.predict_proba(&input).iter().zip(model.predict_proba(&input)) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Classifiers module
+//!
+//! This module contains models that identify ciphers from statistical features: a wrapper
+//! around the fixed z-score tables used by `identify_cipher::get_cipher`, a trainable neural
+//! network, and an ensemble that combines several of them.
+
+pub mod ensemble;
+pub mod features;
+pub mod mlp;
+pub mod profile;
+pub mod zscore;
+
+/// Small deterministic pseudo-random number generator (SplitMix64)
+///
+/// Used for weight initialization and shuffling so that training runs are
+/// reproducible from a seed without pulling in an external RNG crate.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from the given seed
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Returns the next 64 random bits
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniform sample in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a sample from the standard normal distribution (Box-Muller)
+    pub(crate) fn next_normal(&mut self) -> f64 {
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Shuffles a slice in place (Fisher-Yates)
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// A model that assigns a probability to each cipher type for a ciphertext
+///
+/// Implementations can be combined with [`ensemble::Ensemble`].
+pub trait Classifier {
+    /// Short name used in reports
+    fn name(&self) -> &str;
+
+    /// Returns a (cipher_type, probability) pair for every cipher type the model knows,
+    /// with probabilities summing to 1
+    fn probabilities(&self, text: &str) -> Vec<(String, f64)>;
+}
+
+impl Classifier for mlp::Mlp {
+    fn name(&self) -> &str {
+        "mlp"
+    }
+
+    fn probabilities(&self, text: &str) -> Vec<(String, f64)> {
+        let probabilities = self.predict_proba(&features::extract_features(text));
+        self.classes.iter().cloned().zip(probabilities).collect()
+    }
+}
@@ -0,0 +1,128 @@
+//! Profile Classifier
+//!
+//! This module implements a trainable version of the z-score tables used by
+//! `identify_cipher::get_cipher`: the mean and standard deviation of each statistic are
+//! estimated per cipher type from labeled data instead of being hardcoded.
+
+use std::error::Error;
+
+use crate::classifiers::Classifier;
+use crate::identify_cipher::{CIPHER_TYPES, CipherScore, STAT_NAMES, get_cipher_stats};
+
+/// Smallest standard deviation used when scoring, to avoid division by zero
+const MIN_STD: f64 = 0.001;
+
+/// Per-cipher mean and standard deviation of each statistic, estimated from labeled data
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::classifiers::profile::ProfileClassifier;
+///
+/// let stats = vec![vec![40.0; 9], vec![42.0; 9], vec![66.0; 9], vec![68.0; 9]];
+/// let labels: Vec<String> = ["Vigenere", "Vigenere", "columnar", "columnar"].iter().map(|s| s.to_string()).collect();
+/// let profile = ProfileClassifier::fit(&stats, &labels).unwrap();
+///
+/// let scores = profile.score_stats(&[41.0; 9]);
+/// assert!(scores[0].1 < scores[1].1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProfileClassifier {
+    /// Cipher types in the training data, in table order
+    pub classes: Vec<String>,
+
+    /// Mean of each statistic, indexed by class then statistic
+    pub mean: Vec<Vec<f64>>,
+
+    /// Standard deviation of each statistic, indexed by class then statistic
+    pub std: Vec<Vec<f64>>,
+}
+
+impl ProfileClassifier {
+    /// Estimates the profiles from statistics vectors
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - Statistics for each sample; only the first `STAT_NAMES.len()` values are used
+    /// * `labels` - The cipher type of each sample
+    pub fn fit(stats: &[Vec<f64>], labels: &[String]) -> Result<Self, Box<dyn Error>> {
+        if stats.is_empty() || stats.len() != labels.len() {
+            return Err("Profiles need a non-empty set of statistics with one label each".into());
+        }
+        if stats.iter().any(|s| s.len() < STAT_NAMES.len()) {
+            return Err(format!("Every sample must have at least {} statistics", STAT_NAMES.len()).into());
+        }
+
+        let mut classes: Vec<String> = labels.to_vec();
+        classes.sort_by_key(|c| (CIPHER_TYPES.iter().position(|t| t == c).unwrap_or(usize::MAX), c.clone()));
+        classes.dedup();
+
+        let mut mean = Vec::with_capacity(classes.len());
+        let mut std = Vec::with_capacity(classes.len());
+        for class in &classes {
+            let samples: Vec<&Vec<f64>> = stats.iter().zip(labels).filter(|(_, l)| *l == class).map(|(s, _)| s).collect();
+            let n = samples.len() as f64;
+
+            let m: Vec<f64> = (0..STAT_NAMES.len()).map(|j| samples.iter().map(|s| s[j]).sum::<f64>() / n).collect();
+            let sd: Vec<f64> = (0..STAT_NAMES.len())
+                .map(|j| (samples.iter().map(|s| (s[j] - m[j]).powi(2)).sum::<f64>() / n).sqrt())
+                .collect();
+            mean.push(m);
+            std.push(sd);
+        }
+
+        Ok(ProfileClassifier { classes, mean, std })
+    }
+
+    /// Scores every class for the given statistics, like `identify_cipher::get_cipher`
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - Statistics in `STAT_NAMES` order
+    ///
+    /// # Returns
+    ///
+    /// An unsorted vector of (cipher_type, score) pairs, where lower scores indicate better matches
+    pub fn score_stats(&self, stats: &[f64]) -> Vec<CipherScore> {
+        self.classes
+            .iter()
+            .enumerate()
+            .map(|(i, class)| {
+                let z: f64 = (0..STAT_NAMES.len())
+                    .map(|j| ((stats[j] - self.mean[i][j]) / self.std[i][j].max(MIN_STD)).abs())
+                    .sum();
+                (class.clone(), z)
+            })
+            .collect()
+    }
+}
+
+impl Classifier for ProfileClassifier {
+    fn name(&self) -> &str {
+        "profile"
+    }
+
+    fn probabilities(&self, text: &str) -> Vec<(String, f64)> {
+        let scores = self.score_stats(&get_cipher_stats(text));
+        let best = scores.iter().map(|(_, s)| *s).fold(f64::INFINITY, f64::min);
+        let weights: Vec<f64> = scores.iter().map(|(_, s)| (best - s).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        scores.into_iter().zip(weights).map(|((c, _), w)| (c, w / total)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_mean_and_std() {
+        let stats = vec![vec![1.0; 9], vec![3.0; 9]];
+        let labels = vec!["bifid".to_string(), "bifid".to_string()];
+        let profile = ProfileClassifier::fit(&stats, &labels).unwrap();
+
+        assert_eq!(profile.classes, vec!["bifid"]);
+        assert_eq!(profile.mean[0][0], 2.0);
+        assert_eq!(profile.std[0][0], 1.0);
+    }
+}
@@ -0,0 +1,75 @@
+//! Z-score Classifier
+//!
+//! This module exposes the table-based scoring of `identify_cipher::get_cipher` through the
+//! [`Classifier`] trait so it can be combined with other models.
+
+use crate::classifiers::Classifier;
+use crate::identify_cipher::{cipher_type_names, get_cipher, get_cipher_stats};
+
+/// Classifier based on the summed z-scores from `identify_cipher::get_cipher`
+///
+/// Z-scores are turned into probabilities with a softmax over `-score / temperature`,
+/// so larger temperatures give flatter distributions.
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::classifiers::Classifier;
+/// use cipher_identifier::classifiers::zscore::ZScoreClassifier;
+///
+/// let classifier = ZScoreClassifier::default();
+/// let probabilities = classifier.probabilities("THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG");
+/// let total: f64 = probabilities.iter().map(|(_, p)| p).sum();
+/// assert!((total - 1.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZScoreClassifier {
+    /// Softmax temperature applied to the summed z-scores
+    pub temperature: f64,
+}
+
+impl Default for ZScoreClassifier {
+    fn default() -> Self {
+        ZScoreClassifier { temperature: 1.0 }
+    }
+}
+
+impl Classifier for ZScoreClassifier {
+    fn name(&self) -> &str {
+        "zscore"
+    }
+
+    fn probabilities(&self, text: &str) -> Vec<(String, f64)> {
+        let scores = get_cipher(&get_cipher_stats(text), &cipher_type_names());
+        let best = scores.iter().map(|(_, s)| *s).fold(f64::INFINITY, f64::min);
+
+        let weights: Vec<f64> = scores
+            .iter()
+            .map(|(_, s)| (-(s - best) / self.temperature).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        scores
+            .into_iter()
+            .zip(weights)
+            .map(|((cipher, _), w)| (cipher, w / total))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_zscore_is_most_probable() {
+        let text = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+        let best = crate::identify_cipher::identify_cipher(text, 1, None);
+        let probabilities = ZScoreClassifier { temperature: 2.0 }.probabilities(text);
+        let most_probable = probabilities
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .unwrap();
+        assert_eq!(most_probable.0, best[0].0);
+    }
+}
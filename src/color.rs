@@ -0,0 +1,91 @@
+//! Color Module
+//!
+//! This module decides whether output is colored, and prints tables accordingly.
+//!
+//! Colors are used when standard output is a terminal and `NO_COLOR` is not set, unless
+//! `--color always` or `--color never` says otherwise. The decision is applied to `colored`
+//! globally, and [`print_table`] follows it for prettytable style specs.
+
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use clap::ValueEnum;
+use prettytable::Table;
+
+/// Environment variable that turns colors off when set to a non-empty value (see no-color.org)
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// Whether tables are colored even when standard output is not a terminal
+static FORCE_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// When to color the output
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color if standard output is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always color, even when redirected
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Decides whether to color
+    ///
+    /// # Arguments
+    ///
+    /// * `no_color` - Whether `NO_COLOR` is set to a non-empty value
+    /// * `is_terminal` - Whether standard output is a terminal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::color::ColorChoice;
+    ///
+    /// assert!(ColorChoice::Auto.should_color(false, true));
+    /// assert!(!ColorChoice::Auto.should_color(true, true));
+    /// assert!(!ColorChoice::Auto.should_color(false, false));
+    /// assert!(ColorChoice::Always.should_color(true, false));
+    /// ```
+    pub fn should_color(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && is_terminal,
+        }
+    }
+
+    /// Applies the choice to all output of the process, from the environment and standard output
+    pub fn apply(self) {
+        let no_color = std::env::var_os(NO_COLOR_ENV_VAR).is_some_and(|value| !value.is_empty());
+        colored::control::set_override(self.should_color(no_color, io::stdout().is_terminal()));
+        FORCE_COLOR.store(self == ColorChoice::Always, Ordering::Relaxed);
+    }
+}
+
+/// Prints a table to standard output, with its style specs only if output is colored
+///
+/// Without [`ColorChoice::apply`], tables are colored on terminals, as with `Table::printstd`.
+/// Failures to print are ignored.
+pub fn print_table(table: &Table) {
+    if colored::control::SHOULD_COLORIZE.should_colorize() {
+        let _ = table.print_tty(FORCE_COLOR.load(Ordering::Relaxed));
+    } else {
+        let _ = table.print(&mut io::stdout());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_choice_overrides_environment() {
+        for (no_color, is_terminal) in [(false, false), (false, true), (true, false), (true, true)] {
+            assert!(ColorChoice::Always.should_color(no_color, is_terminal));
+            assert!(!ColorChoice::Never.should_color(no_color, is_terminal));
+        }
+        assert_eq!(ColorChoice::default(), ColorChoice::Auto);
+    }
+}
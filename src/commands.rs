@@ -0,0 +1,210 @@
+//! Commands Module
+//!
+//! This module runs the subcommands of the CLI other than `analyze` and `stats`: the corpus
+//! tools (`benchmark`, `train`, `crossval`, `ablation`, `calibrate`), the catalog (`list`,
+//! `describe`), `compare`, `repl`, and the generation of shell completions and the man page.
+//!
+//! Each function takes the parsed arguments of its subcommand and the cipher catalog. Labeled
+//! corpora are resolved against the catalog, so that they may use any alias of a cipher.
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::ablation;
+use crate::benchmark::{CipherTestCase, benchmark, canonicalize_labels, load_test_cases};
+use crate::calibration::Calibration;
+use crate::cipher_analyzer::{
+    AblationArgs, BIN_NAME, BenchmarkArgs, CalibrateArgs, CompareArgs, CompletionsArgs, CrossvalArgs, DescribeArgs,
+    ListArgs, ReplArgs, TrainArgs, cli_command,
+};
+use crate::classifiers::ensemble::{Ensemble, LearnedWeights};
+use crate::classifiers::mlp::{Mlp, MlpConfig};
+use crate::classifiers::zscore::ZScoreClassifier;
+use crate::compare::compare_ciphers;
+use crate::crossval::{self, ModelKind};
+use crate::display::{describe_cipher, display_catalog, display_comparison};
+use crate::identify_cipher;
+use crate::input::read_inputs;
+use crate::models::catalog::CipherCatalog;
+use crate::repl;
+
+/// Measures the top-5 accuracy of the z-score tables on a labeled corpus
+pub fn run_benchmark(args: BenchmarkArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let (correct, total) = benchmark(&args.data, catalog)?;
+    println!("\n{}/{} correct", correct, total);
+    println!("{:.2}% accuracy", correct as f64 / total as f64 * 100.0);
+    Ok(())
+}
+
+/// Trains a neural network on a labeled corpus and saves its weights
+pub fn run_train(args: TrainArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let cases = load_labeled_cases(&args.data, catalog)?;
+    let mut config = MlpConfig { seed: args.seed, ..MlpConfig::default() };
+    if let Some(hidden_layers) = args.hidden_layers {
+        config.hidden_layers = hidden_layers;
+    }
+    if let Some(learning_rate) = args.learning_rate {
+        config.learning_rate = learning_rate;
+    }
+    if let Some(epochs) = args.epochs {
+        config.max_epochs = epochs;
+    }
+
+    let (model, report) = Mlp::train(&cases, &config)?;
+    model.save(&args.output)?;
+    if let Some(best) = report.epochs.iter().find(|e| e.epoch == report.best_epoch) {
+        println!(
+            "Best epoch: {} of {} (validation loss {:.4}, validation accuracy {:.2}%)",
+            best.epoch,
+            report.epochs.len(),
+            best.validation_loss,
+            best.validation_accuracy * 100.0
+        );
+    }
+    if report.stopped_early {
+        println!("Stopped early because the validation loss stopped improving");
+    }
+    println!("Model written to {}", args.output.display());
+    Ok(())
+}
+
+/// Cross-validates a model, or searches the neural network hyperparameters with `--search`
+pub fn run_crossval(args: CrossvalArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let cases = load_labeled_cases(&args.data, catalog)?;
+
+    if args.search {
+        let results = crossval::grid_search(&cases, &crossval::default_grid(), args.folds, args.seed)?;
+        crossval::print_grid_search(&results, args.folds);
+        return Ok(());
+    }
+
+    let model = match args.model {
+        ModelKind::Mlp(mut config) => {
+            config.seed = args.seed;
+            if let Some(hidden_layers) = args.hidden_layers {
+                config.hidden_layers = hidden_layers;
+            }
+            if let Some(learning_rate) = args.learning_rate {
+                config.learning_rate = learning_rate;
+            }
+            if let Some(epochs) = args.epochs {
+                config.max_epochs = epochs;
+            }
+            config.validate()?;
+            ModelKind::Mlp(config)
+        }
+        other => other,
+    };
+    crossval::run_cross_validation(&cases, &model, args.folds, args.seed)
+}
+
+/// Measures how much each statistical test contributes to identification accuracy
+pub fn run_ablation(args: AblationArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let cases = load_labeled_cases(&args.data, catalog)?;
+    let report = match args.model {
+        ModelKind::ZScore => ablation::ablate_tables(&cases)?,
+        ref kind => ablation::permutation_importance(&cases, kind, args.repeats, args.seed)?,
+    };
+    ablation::print_report(&report, args.per_cipher);
+    Ok(())
+}
+
+/// Fits the calibration on a labeled corpus, with the ensemble weights of `--model` if given
+pub fn run_calibrate(args: CalibrateArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let cases = load_labeled_cases(&args.data, catalog)?;
+    let mut calibration = Calibration::fit(&cases, args.coverage)?;
+    if let Some(model_path) = args.model {
+        let mut ensemble = Ensemble::new(args.combine)
+            .with(Box::new(ZScoreClassifier { temperature: calibration.temperature }), 0.5)
+            .with(Box::new(Mlp::load(model_path)?), 0.5);
+        let weights = ensemble.learn_weights(&cases, catalog, args.step)?;
+        calibration.ensemble = Some(LearnedWeights { combine: args.combine, weights });
+    }
+    calibration.save(&args.output)?;
+    println!("Temperature: {:.4}", calibration.temperature);
+    println!("Reject threshold: {:.4}", calibration.reject_threshold);
+    if let Some(ensemble) = &calibration.ensemble {
+        println!(
+            "Ensemble weights: {:.2} z-score tables, {:.2} network",
+            ensemble.weights[0], ensemble.weights[1]
+        );
+    }
+    println!("Calibration written to {}", args.output.display());
+    Ok(())
+}
+
+/// Lists the supported ciphers, optionally of one family or table only
+pub fn run_list(args: ListArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let mut names = catalog.names();
+    if let Some(family) = &args.family {
+        let members = catalog.family(family);
+        names.retain(|name| members.contains(name));
+    }
+    if let Some(table) = &args.table {
+        let members = catalog.with_table(table);
+        names.retain(|name| members.contains(name));
+    }
+    display_catalog(catalog, &names, args.format)
+}
+
+/// Shows the metadata of a supported cipher
+pub fn run_describe(args: DescribeArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let name = catalog.resolve(&args.cipher)?;
+    describe_cipher(name, &catalog.cipher_types()[name], args.format)
+}
+
+/// Compares how well two ciphers fit a ciphertext
+pub fn run_compare(args: CompareArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let a = catalog.resolve(&args.a)?;
+    let b = catalog.resolve(&args.b)?;
+    let calibration = match args.calibration {
+        Some(path) => Calibration::load(path)?,
+        None => Calibration::default(),
+    };
+    let text = read_text(args.text, args.file)?;
+    let comparison = compare_ciphers(&identify_cipher::get_cipher_stats(&text), a, b, &calibration)?;
+    display_comparison(&comparison);
+    Ok(())
+}
+
+/// Starts an interactive session, with the given ciphertext if any
+pub fn run_repl(args: ReplArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    let calibration = match args.calibration {
+        Some(path) => Calibration::load(path)?,
+        None => Calibration::default(),
+    };
+    let initial = if args.text.is_some() || args.file.is_some() {
+        Some(read_text(args.text, args.file)?)
+    } else {
+        None
+    };
+    repl::run_repl(std::io::stdin().lock(), initial, &calibration, catalog)
+}
+
+/// Prints a completion script that completes the cipher names of the catalog
+pub fn run_completions(args: CompletionsArgs, catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    clap_complete::generate(args.shell, &mut cli_command(catalog), BIN_NAME, &mut std::io::stdout());
+    Ok(())
+}
+
+/// Prints the man page in roff format
+pub fn run_man(catalog: &CipherCatalog) -> Result<(), Box<dyn Error>> {
+    clap_mangen::Man::new(cli_command(catalog)).render(&mut std::io::stdout())?;
+    Ok(())
+}
+
+/// Gets a single ciphertext from a file, the command line or standard input
+fn read_text(text: Option<String>, file: Option<PathBuf>) -> Result<String, Box<dyn Error>> {
+    let mut inputs = read_inputs(text, file.as_slice(), false)?;
+    if inputs.len() > 1 {
+        return Err("Expected a single ciphertext; pass either --text or --file".into());
+    }
+    Ok(inputs.remove(0).text)
+}
+
+/// Loads a labeled corpus and resolves its labels to canonical cipher names
+fn load_labeled_cases(path: &PathBuf, catalog: &CipherCatalog) -> Result<Vec<CipherTestCase>, Box<dyn Error>> {
+    let mut cases = load_test_cases(path)?;
+    canonicalize_labels(&mut cases, catalog)?;
+    Ok(cases)
+}
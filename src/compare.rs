@@ -0,0 +1,138 @@
+//! Compare Module
+//!
+//! This module compares two cipher types head-to-head on one ciphertext, using the same
+//! per-cipher mean and standard deviation tables as `identify_cipher::get_cipher`.
+
+use serde::Serialize;
+
+use crate::calibration::Calibration;
+use crate::identify_cipher::{self, STAT_NAMES};
+
+/// Statistics whose absolute z-scores differ by at least this much are considered discriminating
+pub const DISCRIMINATING_DIFFERENCE: f64 = 1.0;
+
+/// How one statistical test fits each of the two compared ciphers
+#[derive(Debug, Clone, Serialize)]
+pub struct StatComparison {
+    /// Name of the statistical test
+    pub stat: &'static str,
+
+    /// Value of the statistic for the text
+    pub observed: f64,
+
+    /// z-score of the statistic for the first cipher
+    pub z_a: f64,
+
+    /// z-score of the statistic for the second cipher
+    pub z_b: f64,
+}
+
+impl StatComparison {
+    /// Returns how much better the statistic fits the first cipher; negative values favor the second
+    pub fn advantage(&self) -> f64 {
+        self.z_b.abs() - self.z_a.abs()
+    }
+
+    /// Returns whether the statistic separates the two ciphers
+    pub fn is_discriminating(&self) -> bool {
+        self.advantage().abs() >= DISCRIMINATING_DIFFERENCE
+    }
+}
+
+/// The head-to-head comparison of two cipher types on one text
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    /// The first cipher
+    pub a: String,
+
+    /// The second cipher
+    pub b: String,
+
+    /// Score of the first cipher (lower is better)
+    pub score_a: f64,
+
+    /// Score of the second cipher (lower is better)
+    pub score_b: f64,
+
+    /// Probability of the first cipher if the text is one of the two
+    pub probability_a: f64,
+
+    /// Per-statistic comparison in `STAT_NAMES` order
+    pub stats: Vec<StatComparison>,
+}
+
+impl Comparison {
+    /// Returns the name of the cipher that fits the text better
+    pub fn favored(&self) -> &str {
+        if self.score_a <= self.score_b { &self.a } else { &self.b }
+    }
+
+    /// Returns the statistics that separate the two ciphers, most discriminating first
+    pub fn discriminating(&self) -> Vec<&StatComparison> {
+        let mut stats: Vec<&StatComparison> = self.stats.iter().filter(|s| s.is_discriminating()).collect();
+        stats.sort_by(|x, y| y.advantage().abs().partial_cmp(&x.advantage().abs()).unwrap_or(std::cmp::Ordering::Equal));
+        stats
+    }
+}
+
+/// Compares two cipher types on the statistics of a text
+///
+/// # Arguments
+///
+/// * `cipher_stats` - Statistics of the text in `STAT_NAMES` order
+/// * `a` - The first cipher type
+/// * `b` - The second cipher type
+/// * `calibration` - The calibration used to turn the score difference into a probability
+///
+/// # Returns
+///
+/// The comparison, or an error if either cipher type has no statistics tables
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::calibration::Calibration;
+/// use cipher_identifier::compare::compare_ciphers;
+/// use cipher_identifier::identify_cipher::get_cipher_stats;
+///
+/// let stats = get_cipher_stats("THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG");
+/// let comparison = compare_ciphers(&stats, "Vigenere", "Beaufort", &Calibration::default()).unwrap();
+/// assert_eq!(comparison.stats.len(), 9);
+/// assert!(comparison.probability_a > 0.0 && comparison.probability_a < 1.0);
+/// ```
+pub fn compare_ciphers(
+    cipher_stats: &[f64],
+    a: &str,
+    b: &str,
+    calibration: &Calibration,
+) -> Result<Comparison, Box<dyn std::error::Error>> {
+    let z_a = identify_cipher::get_stat_z_scores(cipher_stats, a).ok_or_else(|| format!("{} has no statistics tables", a))?;
+    let z_b = identify_cipher::get_stat_z_scores(cipher_stats, b).ok_or_else(|| format!("{} has no statistics tables", b))?;
+
+    let score_a: f64 = z_a.iter().map(|z| z.abs()).sum();
+    let score_b: f64 = z_b.iter().map(|z| z.abs()).sum();
+    let probability_a = 1.0 / (1.0 + ((score_a - score_b) / calibration.temperature).exp());
+
+    let stats = (0..STAT_NAMES.len())
+        .map(|j| StatComparison { stat: STAT_NAMES[j], observed: cipher_stats[j], z_a: z_a[j], z_b: z_b[j] })
+        .collect();
+
+    Ok(Comparison { a: a.to_string(), b: b.to_string(), score_a, score_b, probability_a, stats })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparison_is_symmetric() {
+        let stats = identify_cipher::get_cipher_stats("LXFOPVEFRNHRLXFOPVEFRNHRQWERTY");
+        let calibration = Calibration { temperature: 2.0, reject_threshold: 3.0, ensemble: None };
+        let ab = compare_ciphers(&stats, "Quagmire III", "Quagmire IV", &calibration).unwrap();
+        let ba = compare_ciphers(&stats, "Quagmire IV", "Quagmire III", &calibration).unwrap();
+
+        assert_eq!(ab.favored(), ba.favored());
+        assert!((ab.probability_a + ba.probability_a - 1.0).abs() < 1e-12);
+        assert_eq!(ab.discriminating().len(), ba.discriminating().len());
+    }
+}
@@ -0,0 +1,228 @@
+//! Config Module
+//!
+//! This module loads default analysis settings from a configuration file, so that a team can
+//! share settings without long command lines. Each key maps onto a command line option of the
+//! same name, and options given on the command line always take precedence.
+//!
+//! Files are looked up in this order, the first one setting a key winning:
+//!
+//! 1. the file given with `--config`, which replaces the lookup of the others
+//! 2. `.cipher_identifier.toml` or `.cipher_identifier.json` in the current directory or the
+//!    nearest parent directory that has one
+//! 3. `cipher_identifier/config.toml` or `cipher_identifier/config.json` in the user's
+//!    configuration directory (`$XDG_CONFIG_HOME`, `~/.config` or `%APPDATA%`)
+//!
+//! Relative paths in a file are resolved against the directory of that file.
+//!
+//! A language profile and normalization options are out of scope: the statistics tables are
+//! fixed, and ciphertexts are always stripped of whitespace and uppercased, so there are no
+//! such command line options for a file to set.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::priors::PRESETS;
+
+/// Names of the project-local configuration files, in order of preference
+pub const PROJECT_CONFIG_FILES: [&str; 2] = [".cipher_identifier.toml", ".cipher_identifier.json"];
+
+/// Names of the user-level configuration files within the configuration directory
+pub const USER_CONFIG_FILES: [&str; 2] = ["cipher_identifier/config.toml", "cipher_identifier/config.json"];
+
+/// Default settings read from a configuration file
+///
+/// Every field is optional; unset fields fall back to the next configuration file and then to
+/// the built-in defaults.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The top n most likely ciphers to display
+    pub number: Option<usize>,
+
+    /// Output format of the analysis and the `stats` subcommand
+    pub format: Option<String>,
+
+    /// Verbosity level of the table output
+    pub verbose: Option<u8>,
+
+    /// Weights file of a trained neural network to combine with the z-score tables
+    pub model: Option<PathBuf>,
+
+    /// How to combine the z-score tables with the model
+    pub combine: Option<String>,
+
+    /// Weights of the z-score tables and the model
+    pub weights: Option<Vec<f64>>,
+
+    /// Calibration file written by the `calibrate` subcommand
+    pub calibration: Option<PathBuf>,
+
+    /// Distance above which no supported cipher is considered to fit
+    pub reject_threshold: Option<f64>,
+
+    /// Priors preset or JSON file
+    pub priors: Option<String>,
+
+    /// Only consider these ciphers
+    pub only: Option<Vec<String>>,
+
+    /// Never consider these ciphers
+    pub exclude: Option<Vec<String>>,
+
+    /// Only consider ciphers of these families or tables
+    pub family: Option<Vec<String>>,
+
+    /// Cipher metadata file to use instead of the bundled one
+    pub catalog: Option<PathBuf>,
+}
+
+impl Config {
+    /// Loads a configuration file, in JSON if its extension is `.json` and in TOML otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the configuration file
+    ///
+    /// # Returns
+    ///
+    /// The settings, with relative paths resolved against the directory of the file, or an
+    /// error if the file cannot be read or has an unknown key
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let config: Config = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content).map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?
+        } else {
+            toml::from_str(&content).map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?
+        };
+        Ok(config.relative_to(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    /// Finds and merges the configuration files that apply
+    ///
+    /// # Arguments
+    ///
+    /// * `explicit` - The file given with `--config`, which is used alone if given
+    /// * `dir` - The directory to start the lookup of a project-local file from
+    ///
+    /// # Returns
+    ///
+    /// The merged settings, empty if there is no configuration file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::config::Config;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// std::fs::write(dir.path().join(".cipher_identifier.toml"), "number = 10\n").unwrap();
+    ///
+    /// let config = Config::discover(None, dir.path()).unwrap();
+    /// assert_eq!(config.number, Some(10));
+    /// ```
+    pub fn discover(explicit: Option<&Path>, dir: &Path) -> Result<Self, Box<dyn Error>> {
+        if let Some(path) = explicit {
+            return Self::load(path);
+        }
+
+        let project = dir
+            .ancestors()
+            .flat_map(|ancestor| PROJECT_CONFIG_FILES.iter().map(move |name| ancestor.join(name)))
+            .find(|path| path.is_file());
+        let user = user_config_dir()
+            .and_then(|config_dir| USER_CONFIG_FILES.iter().map(|name| config_dir.join(name)).find(|path| path.is_file()));
+
+        let mut config = Config::default();
+        for path in [project, user].into_iter().flatten() {
+            config = config.or(Self::load(path)?);
+        }
+        Ok(config)
+    }
+
+    /// Fills the unset fields of these settings from `fallback`
+    pub fn or(self, fallback: Config) -> Config {
+        Config {
+            number: self.number.or(fallback.number),
+            format: self.format.or(fallback.format),
+            verbose: self.verbose.or(fallback.verbose),
+            model: self.model.or(fallback.model),
+            combine: self.combine.or(fallback.combine),
+            weights: self.weights.or(fallback.weights),
+            calibration: self.calibration.or(fallback.calibration),
+            reject_threshold: self.reject_threshold.or(fallback.reject_threshold),
+            priors: self.priors.or(fallback.priors),
+            only: self.only.or(fallback.only),
+            exclude: self.exclude.or(fallback.exclude),
+            family: self.family.or(fallback.family),
+            catalog: self.catalog.or(fallback.catalog),
+        }
+    }
+
+    /// Resolves the relative paths of these settings against `dir`
+    fn relative_to(mut self, dir: &Path) -> Config {
+        for path in [&mut self.model, &mut self.calibration, &mut self.catalog].into_iter().flatten() {
+            *path = dir.join(&*path);
+        }
+        // Priors are either a preset name or a path
+        if let Some(priors) = &mut self.priors
+            && !PRESETS.iter().any(|p| p.eq_ignore_ascii_case(priors))
+        {
+            *priors = dir.join(&*priors).to_string_lossy().into_owned();
+        }
+        self
+    }
+}
+
+/// Returns the user's configuration directory, if it can be determined
+fn user_config_dir() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    non_empty("XDG_CONFIG_HOME")
+        .or_else(|| non_empty("HOME").map(|home| home.join(".config")))
+        .or_else(|| non_empty("APPDATA"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_resolves_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("team.toml");
+        fs::write(
+            &path,
+            "number = 3\nformat = \"json\"\nmodel = \"models/mlp.json\"\nweights = [0.3, 0.7]\npriors = \"aca\"\nexclude = [\"playfair\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.number, Some(3));
+        assert_eq!(config.format.as_deref(), Some("json"));
+        assert_eq!(config.model, Some(dir.path().join("models/mlp.json")));
+        assert_eq!(config.weights, Some(vec![0.3, 0.7]));
+        assert_eq!(config.priors.as_deref(), Some("aca"));
+        assert_eq!(config.exclude, Some(vec!["playfair".to_string()]));
+
+        fs::write(&path, "language = \"english\"\n").unwrap();
+        assert!(Config::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_explicit_file_replaces_project_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("puzzles");
+        fs::create_dir(&nested).unwrap();
+        fs::write(dir.path().join(".cipher_identifier.json"), r#"{"number": 10, "format": "csv"}"#).unwrap();
+        let explicit = dir.path().join("other.toml");
+        fs::write(&explicit, "number = 2\n").unwrap();
+
+        let found = Config::discover(None, &nested).unwrap();
+        assert_eq!((found.number, found.format.as_deref()), (Some(10), Some("csv")));
+
+        let explicit = Config::discover(Some(&explicit), &nested).unwrap();
+        assert_eq!((explicit.number, explicit.format), (Some(2), None));
+    }
+}
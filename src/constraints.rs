@@ -0,0 +1,190 @@
+//! Constraints Module
+//!
+//! This module checks a ciphertext against the `CipherConstraints` of each cipher type and
+//! removes or penalizes the ciphers that could not have produced it.
+//!
+//! Violations that are impossible to explain by a transcription error (symbols the cipher
+//! cannot produce, letters in a digit cipher, too many different symbols) rule the cipher
+//! out. Violations of the length and doubled-letter rules only add a penalty, because a single
+//! dropped or extra character is enough to break them.
+//!
+//! Ciphers without constraints in the metadata are never ruled out. Of the bundled ciphers,
+//! only compressocrat has none, because its ciphertext alphabet depends on the chosen code
+//! and its length on the plaintext.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::identify_cipher::CipherScore;
+use crate::models::catalog::CipherCatalog;
+use crate::models::cipher_type::CipherConstraints;
+
+/// Symbols considered when checking constraints; everything else is treated as punctuation
+const CIPHER_SYMBOLS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ#0123456789";
+
+/// Score penalty per soft violation, in units of the calibration temperature
+pub const CONSTRAINT_PENALTY: f64 = 5.0;
+
+/// A constraint of a cipher that the ciphertext does not satisfy
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    /// The cipher whose constraint is violated
+    pub cipher: String,
+
+    /// Why the ciphertext cannot, or is unlikely to, come from the cipher
+    pub reason: String,
+
+    /// Whether the cipher was ruled out rather than penalized
+    pub eliminated: bool,
+}
+
+/// Checks a ciphertext against the constraints of one cipher
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext, uppercase
+/// * `constraints` - The constraints to check
+///
+/// # Returns
+///
+/// The reason for each violated constraint, and whether it rules the cipher out
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::constraints::check_constraints;
+/// use cipher_identifier::models::cipher_type::CipherConstraints;
+///
+/// let constraints = CipherConstraints { length_multiple: Some(2), ..Default::default() };
+/// let violations = check_constraints("ABC", &constraints);
+/// assert_eq!(violations, vec![("has 3 letters/digits, which is not a multiple of 2".to_string(), false)]);
+/// ```
+pub fn check_constraints(text: &str, constraints: &CipherConstraints) -> Vec<(String, bool)> {
+    let symbols: Vec<char> = text.chars().filter(|c| CIPHER_SYMBOLS.contains(*c)).collect();
+    let mut violations = Vec::new();
+
+    if constraints.digits_only && symbols.iter().any(|c| !c.is_ascii_digit()) {
+        violations.push(("contains letters, but the cipher only produces digits".to_string(), true));
+    } else if let Some(allowed) = &constraints.symbols
+        && let Some(symbol) = symbols.iter().find(|c| !allowed.contains(**c))
+    {
+        violations.push((format!("contains '{}', which the cipher cannot produce", symbol), true));
+    }
+
+    if let Some(alphabet_size) = constraints.alphabet_size {
+        let distinct = symbols.iter().collect::<HashSet<_>>().len();
+        if distinct > alphabet_size {
+            violations.push((
+                format!("uses {} different symbols, but the cipher has only {}", distinct, alphabet_size),
+                true,
+            ));
+        }
+    }
+
+    if let Some(multiple) = constraints.length_multiple
+        && !symbols.len().is_multiple_of(multiple)
+    {
+        violations.push((format!("has {} letters/digits, which is not a multiple of {}", symbols.len(), multiple), false));
+    }
+
+    if constraints.doubled_letters == Some(false)
+        && let Some(pair) = symbols.chunks_exact(2).find(|pair| pair[0] == pair[1])
+    {
+        violations.push((format!("contains the doubled pair '{}{}'", pair[0], pair[1]), false));
+    }
+
+    violations
+}
+
+/// Removes or penalizes the ciphers whose constraints the ciphertext violates
+///
+/// If every cipher would be ruled out, none are, and all violations are penalized instead.
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext, uppercase
+/// * `scores` - The (cipher_type, score) pairs, lower is better
+/// * `catalog` - Metadata holding the constraints of each cipher
+/// * `temperature` - The calibration temperature the penalty is scaled by
+///
+/// # Returns
+///
+/// The remaining scores, in their original order, and the violations that were found
+pub fn apply_constraints(
+    text: &str,
+    scores: Vec<CipherScore>,
+    catalog: &CipherCatalog,
+    temperature: f64,
+) -> (Vec<CipherScore>, Vec<Violation>) {
+    let mut violations = Vec::new();
+    for (cipher, _) in &scores {
+        if let Some(cipher_type) = catalog.get(cipher) {
+            for (reason, eliminated) in check_constraints(text, &cipher_type.constraints) {
+                violations.push(Violation { cipher: cipher.clone(), reason, eliminated });
+            }
+        }
+    }
+
+    let is_eliminated = |violations: &[Violation], cipher: &str| violations.iter().any(|v| v.eliminated && v.cipher == cipher);
+    if scores.iter().all(|(cipher, _)| is_eliminated(&violations, cipher)) {
+        for violation in &mut violations {
+            violation.eliminated = false;
+        }
+    }
+
+    // Penalties of ciphers that are ruled out anyway are not worth reporting
+    let eliminated: Vec<String> = violations.iter().filter(|v| v.eliminated).map(|v| v.cipher.clone()).collect();
+    violations.retain(|v| v.eliminated || !eliminated.contains(&v.cipher));
+
+    let adjusted = scores
+        .into_iter()
+        .filter(|(cipher, _)| !eliminated.contains(cipher))
+        .map(|(cipher, score)| {
+            let penalties = violations.iter().filter(|v| v.cipher == cipher).count();
+            (cipher, score + penalties as f64 * CONSTRAINT_PENALTY * temperature)
+        })
+        .collect();
+
+    (adjusted, violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letters_rule_out_digit_ciphers() {
+        let catalog = CipherCatalog::bundled();
+        let scores = vec![("tridigital".to_string(), 1.0), ("playfair".to_string(), 2.0), ("Vigenere".to_string(), 3.0)];
+
+        let (adjusted, violations) = apply_constraints("HELLOWORLDX", scores, &catalog, 1.0);
+        let names: Vec<&str> = adjusted.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(names, vec!["playfair", "Vigenere"]);
+        assert!(violations.iter().any(|v| v.cipher == "tridigital" && v.eliminated));
+        // The odd length and the doubled pair 'LL' only penalize playfair
+        assert_eq!(adjusted[0].1, 2.0 + 2.0 * CONSTRAINT_PENALTY);
+    }
+
+    #[test]
+    fn test_nothing_eliminated_if_all_would_be() {
+        let catalog = CipherCatalog::bundled();
+        let scores = vec![("tridigital".to_string(), 1.0), ("pollux".to_string(), 2.0)];
+
+        let (adjusted, violations) = apply_constraints("ABCD", scores, &catalog, 1.0);
+        assert_eq!(adjusted.len(), 2);
+        assert!(violations.iter().all(|v| !v.eliminated));
+    }
+
+    #[test]
+    fn test_only_compressocrat_is_unconstrained() {
+        let catalog = CipherCatalog::bundled();
+        let unconstrained: Vec<&String> = catalog
+            .cipher_types()
+            .iter()
+            .filter(|(_, c)| c.constraints == CipherConstraints::default())
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(unconstrained, vec!["compressocrat"]);
+    }
+}
@@ -0,0 +1,442 @@
+//! Cross-validation Module
+//!
+//! This module evaluates identification models with stratified k-fold cross-validation on a
+//! labeled corpus, so accuracy is always measured on ciphertexts the model was not fitted on.
+//! It also provides a grid search over neural network hyperparameters.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+use crate::benchmark::CipherTestCase;
+use crate::color::print_table;
+use crate::classifiers::Rng;
+use crate::classifiers::features::extract_features;
+use crate::classifiers::mlp::{Mlp, MlpConfig};
+use crate::classifiers::profile::ProfileClassifier;
+use crate::identify_cipher::{self, CipherScore, STAT_NAMES};
+
+/// The kind of model to evaluate
+#[derive(Debug, Clone)]
+pub enum ModelKind {
+    /// The hardcoded z-score tables; nothing is trained, so every fold uses the same model
+    ZScore,
+
+    /// Z-score profiles re-estimated from each training fold
+    Profile,
+
+    /// A neural network trained on each training fold
+    Mlp(MlpConfig),
+}
+
+impl ModelKind {
+    /// Short name used in reports
+    pub fn name(&self) -> &str {
+        match self {
+            ModelKind::ZScore => "zscore",
+            ModelKind::Profile => "profile",
+            ModelKind::Mlp(_) => "mlp",
+        }
+    }
+}
+
+impl FromStr for ModelKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "zscore" | "tables" => Ok(ModelKind::ZScore),
+            "profile" => Ok(ModelKind::Profile),
+            "mlp" => Ok(ModelKind::Mlp(MlpConfig::default())),
+            _ => Err(format!("Unknown model '{}' (expected 'zscore', 'profile' or 'mlp')", s)),
+        }
+    }
+}
+
+/// Accuracy of a model on one fold
+#[derive(Debug, Clone, Serialize)]
+pub struct FoldResult {
+    /// Number of samples the model was trained on
+    pub train_size: usize,
+
+    /// Number of samples the model was evaluated on
+    pub test_size: usize,
+
+    /// Fraction of test samples whose cipher was ranked first
+    pub top1: f64,
+
+    /// Fraction of test samples whose cipher was ranked in the top five
+    pub top5: f64,
+}
+
+/// Cross-validation results for one model
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossValReport {
+    /// Name of the evaluated model
+    pub model: String,
+
+    /// Results for each fold
+    pub folds: Vec<FoldResult>,
+
+    /// Mean top-1 accuracy over the folds
+    pub top1_mean: f64,
+
+    /// Variance of the top-1 accuracy over the folds
+    pub top1_variance: f64,
+
+    /// Mean top-5 accuracy over the folds
+    pub top5_mean: f64,
+
+    /// Variance of the top-5 accuracy over the folds
+    pub top5_variance: f64,
+}
+
+/// A model trained on one fold
+pub(crate) enum TrainedModel {
+    ZScore,
+    Profile(ProfileClassifier),
+    Mlp(Box<Mlp>),
+}
+
+impl TrainedModel {
+    /// Trains the given kind of model on feature vectors from `extract_features`
+    pub(crate) fn train(kind: &ModelKind, features: &[Vec<f64>], labels: &[String]) -> Result<Self, Box<dyn Error>> {
+        Ok(match kind {
+            ModelKind::ZScore => TrainedModel::ZScore,
+            ModelKind::Profile => TrainedModel::Profile(ProfileClassifier::fit(features, labels)?),
+            ModelKind::Mlp(config) => TrainedModel::Mlp(Box::new(Mlp::train_on_features(features, labels, config)?.0)),
+        })
+    }
+
+    /// Scores a feature vector from `extract_features`; lower scores are better
+    pub(crate) fn score(&self, features: &[f64]) -> Vec<CipherScore> {
+        match self {
+            TrainedModel::ZScore => {
+                identify_cipher::get_cipher(&features[..STAT_NAMES.len()], &identify_cipher::cipher_type_names())
+            }
+            TrainedModel::Profile(profile) => profile.score_stats(features),
+            TrainedModel::Mlp(mlp) => mlp
+                .classes
+                .iter()
+                .cloned()
+                .zip(mlp.predict_proba(features))
+                .map(|(c, p)| (c, -p.max(1e-12).ln()))
+                .collect(),
+        }
+    }
+
+    /// Returns the position of `label` in the ranking for a feature vector, if present
+    pub(crate) fn rank_of(&self, features: &[f64], label: &str) -> Option<usize> {
+        identify_cipher::rank_scores(self.score(features), usize::MAX, None)
+            .iter()
+            .position(|(c, _)| c == label)
+    }
+}
+
+/// Splits sample indices into `k` stratified folds
+///
+/// Samples of each cipher type are shuffled and dealt round-robin, so every fold gets
+/// roughly the same share of each type.
+///
+/// # Arguments
+///
+/// * `labels` - The cipher type of each sample
+/// * `k` - The number of folds
+/// * `seed` - Seed for shuffling
+///
+/// # Returns
+///
+/// `k` vectors of sample indices
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::crossval::stratified_folds;
+///
+/// let labels: Vec<String> = ["a", "a", "b", "b"].iter().map(|s| s.to_string()).collect();
+/// let folds = stratified_folds(&labels, 2, 0);
+/// assert_eq!(folds.len(), 2);
+/// assert_eq!(folds[0].len(), 2);
+/// ```
+pub fn stratified_folds(labels: &[String], k: usize, seed: u64) -> Vec<Vec<usize>> {
+    let mut by_label: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, label) in labels.iter().enumerate() {
+        by_label.entry(label).or_default().push(i);
+    }
+
+    let mut rng = Rng::new(seed);
+    let k = k.max(1);
+    let mut folds = vec![Vec::new(); k];
+    let mut next = 0;
+    for indices in by_label.values_mut() {
+        rng.shuffle(indices);
+        for &i in indices.iter() {
+            folds[next % k].push(i);
+            next += 1;
+        }
+    }
+    folds
+}
+
+/// Runs stratified k-fold cross-validation of a model on a labeled corpus
+///
+/// # Arguments
+///
+/// * `cases` - The labeled corpus
+/// * `kind` - The model to train and evaluate
+/// * `k` - The number of folds (at least 2)
+/// * `seed` - Seed for the fold assignment
+///
+/// # Returns
+///
+/// Per-fold accuracies with their mean and variance
+///
+/// # Examples
+///
+/// ```no_run
+/// use cipher_identifier::benchmark::load_test_cases;
+/// use cipher_identifier::crossval::{cross_validate, ModelKind};
+///
+/// let cases = load_test_cases("data/random_cipher_data.json").unwrap();
+/// let report = cross_validate(&cases, &ModelKind::Profile, 5, 42).unwrap();
+/// println!("top-1: {:.3} ± {:.3}", report.top1_mean, report.top1_variance.sqrt());
+/// ```
+pub fn cross_validate(
+    cases: &[CipherTestCase],
+    kind: &ModelKind,
+    k: usize,
+    seed: u64,
+) -> Result<CrossValReport, Box<dyn Error>> {
+    let features: Vec<Vec<f64>> = cases.iter().map(|c| extract_features(&c.ciphertext)).collect();
+    let labels: Vec<String> = cases.iter().map(|c| c.ciphertype.clone()).collect();
+    cross_validate_features(&features, &labels, kind, k, seed)
+}
+
+/// Runs stratified k-fold cross-validation on precomputed feature vectors
+///
+/// # Arguments
+///
+/// * `features` - Feature vectors from `extract_features`
+/// * `labels` - The cipher type of each feature vector
+/// * `kind` - The model to train and evaluate
+/// * `k` - The number of folds (at least 2)
+/// * `seed` - Seed for the fold assignment
+pub fn cross_validate_features(
+    features: &[Vec<f64>],
+    labels: &[String],
+    kind: &ModelKind,
+    k: usize,
+    seed: u64,
+) -> Result<CrossValReport, Box<dyn Error>> {
+    if k < 2 {
+        return Err("Cross-validation needs at least 2 folds".into());
+    }
+    if features.len() < k {
+        return Err(format!("Cannot split {} samples into {} folds", features.len(), k).into());
+    }
+
+    let folds = stratified_folds(labels, k, seed);
+    let mut results = Vec::with_capacity(k);
+
+    for (f, test_idx) in folds.iter().enumerate() {
+        let train_idx: Vec<usize> = folds
+            .iter()
+            .enumerate()
+            .filter(|(g, _)| *g != f)
+            .flat_map(|(_, fold)| fold.iter().copied())
+            .collect();
+
+        let train_features: Vec<Vec<f64>> = train_idx.iter().map(|&i| features[i].clone()).collect();
+        let train_labels: Vec<String> = train_idx.iter().map(|&i| labels[i].clone()).collect();
+        let model = TrainedModel::train(kind, &train_features, &train_labels)?;
+
+        let mut top1 = 0;
+        let mut top5 = 0;
+        for &i in test_idx {
+            match model.rank_of(&features[i], &labels[i]) {
+                Some(0) => {
+                    top1 += 1;
+                    top5 += 1;
+                }
+                Some(r) if r < 5 => top5 += 1,
+                _ => {}
+            }
+        }
+
+        let n = test_idx.len().max(1) as f64;
+        results.push(FoldResult {
+            train_size: train_idx.len(),
+            test_size: test_idx.len(),
+            top1: top1 as f64 / n,
+            top5: top5 as f64 / n,
+        });
+    }
+
+    let (top1_mean, top1_variance) = mean_and_variance(results.iter().map(|r| r.top1));
+    let (top5_mean, top5_variance) = mean_and_variance(results.iter().map(|r| r.top5));
+
+    Ok(CrossValReport {
+        model: kind.name().to_string(),
+        folds: results,
+        top1_mean,
+        top1_variance,
+        top5_mean,
+        top5_variance,
+    })
+}
+
+/// Cross-validates a neural network for every configuration in a grid
+///
+/// # Arguments
+///
+/// * `cases` - The labeled corpus
+/// * `grid` - The hyperparameter configurations to try
+/// * `k` - The number of folds
+/// * `seed` - Seed for the fold assignment
+///
+/// # Returns
+///
+/// Each configuration with its report, best mean top-1 accuracy first
+pub fn grid_search(
+    cases: &[CipherTestCase],
+    grid: &[MlpConfig],
+    k: usize,
+    seed: u64,
+) -> Result<Vec<(MlpConfig, CrossValReport)>, Box<dyn Error>> {
+    let features: Vec<Vec<f64>> = cases.iter().map(|c| extract_features(&c.ciphertext)).collect();
+    let labels: Vec<String> = cases.iter().map(|c| c.ciphertype.clone()).collect();
+
+    let mut results = Vec::with_capacity(grid.len());
+    for config in grid {
+        let report = cross_validate_features(&features, &labels, &ModelKind::Mlp(config.clone()), k, seed)?;
+        results.push((config.clone(), report));
+    }
+    results.sort_by(|a, b| b.1.top1_mean.partial_cmp(&a.1.top1_mean).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(results)
+}
+
+/// Returns a small default grid of neural network hyperparameters
+///
+/// The grid varies the hidden layer sizes and the learning rate around `MlpConfig::default()`.
+pub fn default_grid() -> Vec<MlpConfig> {
+    let mut grid = Vec::new();
+    for hidden_layers in [vec![32], vec![64], vec![64, 64]] {
+        for learning_rate in [0.003, 0.01, 0.03] {
+            grid.push(MlpConfig { hidden_layers: hidden_layers.clone(), learning_rate, ..MlpConfig::default() });
+        }
+    }
+    grid
+}
+
+/// Returns the mean and population variance of the values
+fn mean_and_variance(values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let n = values.clone().count().max(1) as f64;
+    let mean = values.clone().sum::<f64>() / n;
+    let variance = values.map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+/// Runs cross-validation and prints the results
+///
+/// # Arguments
+///
+/// * `cases` - The labeled corpus
+/// * `kind` - The model to train and evaluate
+/// * `k` - The number of folds
+/// * `seed` - Seed for the fold assignment
+pub fn run_cross_validation(cases: &[CipherTestCase], kind: &ModelKind, k: usize, seed: u64) -> Result<(), Box<dyn Error>> {
+    let report = cross_validate(cases, kind, k, seed)?;
+    print_report(&report);
+    Ok(())
+}
+
+/// Prints a cross-validation report
+pub fn print_report(report: &CrossValReport) {
+    println!("\n{}-fold cross-validation of {}", report.folds.len(), report.model);
+    for (i, fold) in report.folds.iter().enumerate() {
+        println!(
+            "  fold {}: train {:>5}  test {:>5}  top-1 {:6.2}%  top-5 {:6.2}%",
+            i + 1,
+            fold.train_size,
+            fold.test_size,
+            fold.top1 * 100.0,
+            fold.top5 * 100.0
+        );
+    }
+    println!(
+        "top-1 accuracy: {:.2}% (variance {:.5})",
+        report.top1_mean * 100.0,
+        report.top1_variance
+    );
+    println!(
+        "top-5 accuracy: {:.2}% (variance {:.5})",
+        report.top5_mean * 100.0,
+        report.top5_variance
+    );
+}
+
+/// Prints the results of a hyperparameter search as a table, best first
+///
+/// # Arguments
+///
+/// * `results` - Each configuration with its report, as returned by [`grid_search`]
+/// * `k` - The number of folds
+pub fn print_grid_search(results: &[(MlpConfig, CrossValReport)], k: usize) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Hidden layers").style_spec("Fc"),
+        Cell::new("Learning rate").style_spec("Fc"),
+        Cell::new("Top-1 mean").style_spec("Fb"),
+        Cell::new("Top-1 variance").style_spec("Fb"),
+        Cell::new("Top-5 mean").style_spec("Fb"),
+        Cell::new("Top-5 variance").style_spec("Fb"),
+    ]));
+    for (config, report) in results {
+        table.add_row(Row::new(vec![
+            Cell::new(&format!("{:?}", config.hidden_layers)),
+            Cell::new(&config.learning_rate.to_string()),
+            Cell::new(&format!("{:.2}%", report.top1_mean * 100.0)),
+            Cell::new(&format!("{:.5}", report.top1_variance)),
+            Cell::new(&format!("{:.2}%", report.top5_mean * 100.0)),
+            Cell::new(&format!("{:.5}", report.top5_variance)),
+        ]));
+    }
+    println!("\n{}", format!("{}-fold hyperparameter search (best first)", k).bold());
+    print_table(&table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stratified_folds_balance_labels() {
+        let labels: Vec<String> = (0..12).map(|i| if i % 3 == 0 { "a" } else { "b" }.to_string()).collect();
+        let folds = stratified_folds(&labels, 4, 7);
+
+        for fold in &folds {
+            assert_eq!(fold.len(), 3);
+            assert_eq!(fold.iter().filter(|&&i| labels[i] == "a").count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_cross_validate_profile() {
+        let mut features = Vec::new();
+        let mut labels = Vec::new();
+        for i in 0..20 {
+            let base = if i % 2 == 0 { 10.0 } else { 50.0 };
+            features.push(vec![base + (i % 5) as f64; 15]);
+            labels.push(if i % 2 == 0 { "Vigenere" } else { "columnar" }.to_string());
+        }
+
+        let report = cross_validate_features(&features, &labels, &ModelKind::Profile, 5, 1).unwrap();
+        assert_eq!(report.folds.len(), 5);
+        assert_eq!(report.top1_mean, 1.0);
+        assert_eq!(report.top1_variance, 0.0);
+    }
+}
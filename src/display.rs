@@ -0,0 +1,730 @@
+//! Display Module
+//!
+//! This module renders the results of the CLI as tables on standard output: the basic stats,
+//! the ranked ciphers with their families, explanations and comparisons, the statistical tests
+//! and the catalog of supported ciphers.
+//!
+//! Tables are printed with [`print_table`], so they follow the `--color` setting. The catalog
+//! can also be printed as JSON, NDJSON or CSV; analysis reports in those formats are printed by
+//! the `analysis` module instead.
+
+use std::collections::HashSet;
+use std::error::Error;
+
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+use crate::calibration::{Calibration, OutOfDistribution};
+use crate::charts::{self, ChartStyle};
+use crate::color::print_table;
+use crate::compare::Comparison;
+use crate::constraints::Violation;
+use crate::hierarchy::{GroupProbability, HierarchicalIdentification};
+use crate::identify_cipher;
+use crate::models::catalog::CipherCatalog;
+use crate::models::cipher_type::CipherType;
+use crate::report::{OutputFormat, StatsReport};
+use crate::similarity::{DistanceMatrix, INDISTINGUISHABLE_DISTANCE};
+use crate::statistical_tests::mic::get_periodic_ics;
+use crate::statistical_tests::{binary_random, ioc, shannon_entropy};
+use crate::watch::RankChange;
+
+/// Largest period shown by the per-period IoC and Kasiski displays
+pub const MAX_PERIOD: usize = 20;
+
+/// A cipher type with its name, as printed by the catalog subcommands
+#[derive(Serialize)]
+struct CatalogEntry<'a> {
+    name: &'a str,
+    #[serde(flatten)]
+    cipher_type: &'a CipherType,
+}
+
+/// Displays basic statistics about the ciphertext
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext to analyze
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_basic_stats;
+///
+/// display_basic_stats("HELLOWORLD");
+/// ```
+pub fn display_basic_stats(text: &str) {
+    let text_length = text.len();
+    let text_ioc = ioc::get_ioc(text);
+    let text_entropy = shannon_entropy::get_shannon_entropy(text);
+    let binary_random_test = binary_random::get_binary_random(text);
+    
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Stat").style_spec("Fc"),
+        Cell::new("Value").style_spec("Fb"),
+    ]));
+    
+    table.add_row(Row::new(vec![
+        Cell::new("Length"),
+        Cell::new(&text_length.to_string()),
+    ]));
+    
+    table.add_row(Row::new(vec![
+        Cell::new("Number of unique characters"),
+        Cell::new(&text.chars().collect::<HashSet<_>>().len().to_string()),
+    ]));
+    
+    table.add_row(Row::new(vec![
+        Cell::new("Missing letters"),
+        Cell::new(&find_missing_letters(text)),
+    ]));
+    
+    table.add_row(Row::new(vec![
+        Cell::new("IoC"),
+        Cell::new(&format!("{:.6}", text_ioc)),
+    ]));
+    
+    table.add_row(Row::new(vec![
+        Cell::new("Shannon entropy"),
+        Cell::new(&format!("{:.6}", text_entropy)),
+    ]));
+    
+    table.add_row(Row::new(vec![
+        Cell::new("Binary random test"),
+        Cell::new(&binary_random_test),
+    ]));
+    
+    println!("\n{}", "Basic stats".bold());
+    print_table(&table);
+}
+
+/// Displays the result of a hierarchical identification
+///
+/// The family and subtype probabilities are shown first, then the ranked ciphers with their
+/// confidences. If the text is too far from every cipher profile, a "no supported cipher
+/// fits" message is shown above the nearest candidates.
+///
+/// # Arguments
+///
+/// * `result` - The identification to display
+/// * `number` - The number of results that were requested
+/// * `highlight` - Optional cipher type to highlight in the results
+/// * `calibration` - The calibration used for the identification
+/// * `catalog` - Metadata used to show the type of each cipher
+pub fn display_identification(
+    result: &HierarchicalIdentification,
+    number: usize,
+    highlight: Option<&str>,
+    calibration: &Calibration,
+    catalog: &CipherCatalog,
+) {
+    display_families(&result.families, &result.subtypes);
+    display_violations(&result.violations);
+    
+    let ciphers = &result.ciphers;
+    if ciphers.rejected {
+        let ood = OutOfDistribution {
+            nearest_profile: ciphers.nearest_profile.clone(),
+            distance: ciphers.ood_distance,
+            signals: ciphers.ood_signals.clone(),
+        };
+        display_rejection(&ood, calibration.reject_threshold);
+    }
+    
+    let scores: Vec<identify_cipher::CipherScore> =
+        ciphers.candidates.iter().map(|c| (c.cipher.clone(), c.score)).collect();
+    let probabilities: Vec<f64> = ciphers.candidates.iter().map(|c| c.probability).collect();
+    display_ranking(&ranking_title(number, ciphers.rejected), &scores, Some(&probabilities), highlight, catalog);
+    if !ciphers.rejected {
+        display_near_ties(&scores);
+    }
+}
+
+/// Displays a ranked list of cipher scores as a table
+///
+/// # Arguments
+///
+/// * `scores` - The ranked (cipher_type, score) pairs, lower is better
+/// * `number` - The number of results that were requested
+/// * `highlight` - Optional cipher type to highlight in the results
+/// * `catalog` - Metadata used to show the type of each cipher
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_scores;
+/// use cipher_identifier::models::catalog::CipherCatalog;
+///
+/// display_scores(vec![("playfair".to_string(), 1.5)], 1, None, &CipherCatalog::bundled());
+/// ```
+pub fn display_scores(
+    scores: Vec<identify_cipher::CipherScore>,
+    number: usize,
+    highlight: Option<&str>,
+    catalog: &CipherCatalog,
+) {
+    display_ranking(&ranking_title(number, false), &scores, None, highlight, catalog);
+    display_near_ties(&scores);
+}
+
+/// Prints a ranking table, with a confidence column if probabilities are given
+pub(crate) fn display_ranking(
+    title: &str,
+    scores: &[identify_cipher::CipherScore],
+    probabilities: Option<&[f64]>,
+    highlight: Option<&str>,
+    catalog: &CipherCatalog,
+) {
+    let mut table = Table::new();
+    let mut titles = vec![
+        Cell::new("Cipher").style_spec("Fc"),
+        Cell::new("Score").style_spec("Fb"),
+    ];
+    if probabilities.is_some() {
+        titles.push(Cell::new("Confidence").style_spec("Fb"));
+    }
+    titles.push(Cell::new("Cipher type").style_spec("Fg"));
+    table.set_titles(Row::new(titles));
+    
+    for (i, (cipher, score)) in scores.iter().enumerate() {
+        let mut values = vec![cipher.clone(), format!("{:.3}", score)];
+        if let Some(probabilities) = probabilities {
+            values.push(format!("{:.1}%", probabilities[i] * 100.0));
+        }
+        values.push(catalog.primary_type(cipher));
+        
+        let style = if Some(cipher.as_str()) == highlight { "Fm" } else { "" };
+        table.add_row(Row::new(values.iter().map(|v| Cell::new(v).style_spec(style)).collect()));
+    }
+    
+    println!("\n{} (lower is better)", title.bold());
+    print_table(&table);
+}
+
+/// Prints the groups of ranked ciphers that the statistics cannot separate
+fn display_near_ties(scores: &[identify_cipher::CipherScore]) {
+    let ciphers: Vec<String> = scores.iter().map(|(c, _)| c.clone()).collect();
+    let matrix = DistanceMatrix::new(&ciphers);
+    
+    for group in matrix.near_ties(scores, INDISTINGUISHABLE_DISTANCE) {
+        println!(
+            "{} {} (their profiles are too close for these statistics to separate them)",
+            "Near tie:".yellow(),
+            group.join(", ")
+        );
+    }
+}
+
+/// Prints the explanation shown when no supported cipher fits the text
+pub(crate) fn display_rejection(ood: &OutOfDistribution, threshold: f64) {
+    println!(
+        "\n{}",
+        "No supported cipher fits this text".bold().red()
+    );
+    if ood.signals.is_empty() {
+        println!(
+            "Its statistics are a median of {:.2} standard deviations from the nearest cipher profile ({}), above the reject threshold of {:.2}.",
+            ood.distance, ood.nearest_profile, threshold
+        );
+    }
+    if !ood.signals.is_empty() {
+        println!("It does not look like a ciphertext:");
+    }
+    for signal in &ood.signals {
+        println!("- {}", signal);
+    }
+}
+
+/// Prints the ciphers that were ruled out or penalized, grouped by reason
+pub(crate) fn display_violations(violations: &[Violation]) {
+    let mut groups: Vec<(&str, bool, Vec<&str>)> = Vec::new();
+    for violation in violations {
+        match groups.iter_mut().find(|(reason, eliminated, _)| *reason == violation.reason && *eliminated == violation.eliminated) {
+            Some((_, _, ciphers)) => ciphers.push(&violation.cipher),
+            None => groups.push((&violation.reason, violation.eliminated, vec![&violation.cipher])),
+        }
+    }
+    
+    for (reason, eliminated, ciphers) in groups {
+        let effect = if eliminated { "Ruled out".red() } else { "Penalized".yellow() };
+        println!("{} {} (text {})", effect, ciphers.join(", "), reason);
+    }
+}
+
+/// Prints the aggregated probabilities of the cipher families and subtypes
+fn display_families(families: &[GroupProbability], subtypes: &[GroupProbability]) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Level").style_spec("Fc"),
+        Cell::new("Group").style_spec("Fg"),
+        Cell::new("Confidence").style_spec("Fb"),
+    ]));
+    
+    for (level, groups) in [("family", families), ("subtype", subtypes)] {
+        for group in groups {
+            table.add_row(Row::new(vec![
+                Cell::new(level),
+                Cell::new(&group.name),
+                Cell::new(&format!("{:.1}%", group.probability * 100.0)),
+            ]));
+        }
+    }
+    
+    println!("\n{}", "Most likely cipher families".bold());
+    print_table(&table);
+}
+
+/// Prints how the ranked ciphers moved since the previous run
+pub(crate) fn display_rank_changes(changes: &[(String, RankChange)]) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Cipher").style_spec("Fc"),
+        Cell::new("Change").style_spec("Fb"),
+    ]));
+    
+    for (cipher, change) in changes {
+        let (label, style) = match change {
+            RankChange::New => ("new".to_string(), "Fy"),
+            RankChange::Up(places) => (format!("up {}", places), "Fg"),
+            RankChange::Down(places) => (format!("down {}", places), "Fr"),
+            RankChange::Unchanged => ("-".to_string(), ""),
+            RankChange::Dropped => ("dropped out".to_string(), "Fr"),
+        };
+        table.add_row(Row::new(vec![Cell::new(cipher).style_spec(style), Cell::new(&label).style_spec(style)]));
+    }
+    
+    println!("\n{}", "Changes since the last run".bold());
+    print_table(&table);
+}
+
+/// Prints the contribution of each statistical test to the score of a cipher
+///
+/// # Arguments
+///
+/// * `stats` - Statistics of the text in `identify_cipher::STAT_NAMES` order
+/// * `cipher` - The cipher type to explain
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_explanation;
+/// use cipher_identifier::identify_cipher::get_cipher_stats;
+///
+/// display_explanation(&get_cipher_stats("THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG"), "Patristocrat");
+/// ```
+pub fn display_explanation(stats: &[f64], cipher: &str) {
+    let Some(contributions) = identify_cipher::explain_cipher(stats, cipher) else {
+        println!("\n{} has no statistics tables to explain", cipher);
+        return;
+    };
+    
+    let total: f64 = contributions.iter().map(|c| c.z_score.abs()).sum();
+    let largest = contributions
+        .iter()
+        .max_by(|a, b| a.z_score.abs().partial_cmp(&b.z_score.abs()).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|c| c.stat);
+    
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Statistic").style_spec("Fc"),
+        Cell::new("Observed").style_spec("Fb"),
+        Cell::new("Expected").style_spec("Fb"),
+        Cell::new("Std dev").style_spec("Fb"),
+        Cell::new("z-score").style_spec("Fb"),
+        Cell::new("Share of score").style_spec("Fg"),
+    ]));
+    
+    for contribution in &contributions {
+        let share = if total > 0.0 { contribution.z_score.abs() / total } else { 0.0 };
+        let style = if Some(contribution.stat) == largest { "Fm" } else { "" };
+        let values = [
+            contribution.stat.to_string(),
+            format!("{:.3}", contribution.observed),
+            format!("{:.3}", contribution.mean),
+            format!("{:.3}", contribution.std),
+            format!("{:+.3}", contribution.z_score),
+            format!("{:.1}%", share * 100.0),
+        ];
+        table.add_row(Row::new(values.iter().map(|v| Cell::new(v).style_spec(style)).collect()));
+    }
+    
+    println!("\n{} (score {:.3})", format!("Why {} scores as it does", cipher).bold(), total);
+    print_table(&table);
+    
+    if let Some(stat) = largest
+        && let Some(contribution) = contributions.iter().find(|c| c.stat == stat)
+        && total > 0.0
+        && contribution.z_score.abs() / total > 0.5
+    {
+        println!("More than half of the score comes from {}, so this ranking rests on a single statistic.", stat);
+    }
+}
+
+/// Prints a head-to-head comparison of two ciphers
+///
+/// Statistics that separate the two ciphers are highlighted, and the favored cipher is
+/// stated with its probability if the text is one of the two.
+///
+/// # Arguments
+///
+/// * `comparison` - The comparison to display
+pub fn display_comparison(comparison: &Comparison) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Statistic").style_spec("Fc"),
+        Cell::new("Observed").style_spec("Fb"),
+        Cell::new(&format!("z ({})", comparison.a)).style_spec("Fb"),
+        Cell::new(&format!("z ({})", comparison.b)).style_spec("Fb"),
+        Cell::new("Favors").style_spec("Fg"),
+    ]));
+    
+    for stat in &comparison.stats {
+        let (favors, style) = if stat.is_discriminating() {
+            let favored = if stat.advantage() > 0.0 { &comparison.a } else { &comparison.b };
+            (favored.as_str(), "Fm")
+        } else {
+            ("-", "")
+        };
+        let values = [
+            stat.stat.to_string(),
+            format!("{:.3}", stat.observed),
+            format!("{:+.3}", stat.z_a),
+            format!("{:+.3}", stat.z_b),
+            favors.to_string(),
+        ];
+        table.add_row(Row::new(values.iter().map(|v| Cell::new(v).style_spec(style)).collect()));
+    }
+    
+    println!("\n{}", format!("{} vs {}", comparison.a, comparison.b).bold());
+    print_table(&table);
+    
+    let favored = comparison.favored();
+    let (probability, other) = if favored == comparison.a {
+        (comparison.probability_a, &comparison.b)
+    } else {
+        (1.0 - comparison.probability_a, &comparison.a)
+    };
+    println!(
+        "{} is favored over {} (score {:.3} vs {:.3}, {:.1}% if the text is one of the two)",
+        favored.bold(),
+        other,
+        comparison.score_a.min(comparison.score_b),
+        comparison.score_a.max(comparison.score_b),
+        probability * 100.0
+    );
+    
+    let discriminating: Vec<&str> = comparison.discriminating().iter().map(|s| s.stat).collect();
+    if discriminating.is_empty() {
+        println!("No statistic separates the two ciphers; the difference is within the noise of the tables.");
+    } else {
+        println!("Discriminating statistics: {}", discriminating.join(", "));
+    }
+}
+
+/// Prints all statistical tests of a stats report
+///
+/// # Arguments
+///
+/// * `report` - The report whose statistics to print
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_statistics;
+/// use cipher_identifier::report::StatsReport;
+///
+/// display_statistics(&StatsReport::new("HELLOWORLD"));
+/// ```
+pub fn display_statistics(report: &StatsReport) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Statistic").style_spec("Fc"),
+        Cell::new("Value").style_spec("Fb"),
+    ]));
+    
+    for (name, value) in &report.statistics {
+        table.add_row(Row::new(vec![Cell::new(name), Cell::new(&format!("{:.6}", value))]));
+    }
+    
+    println!("\n{}", "Statistical tests".bold());
+    print_table(&table);
+}
+
+/// Displays the index of coincidence of the text split into columns, for each period
+///
+/// The period with the highest IoC is highlighted; for a periodic polyalphabetic cipher it
+/// is usually the key length or one of its multiples.
+///
+/// # Arguments
+///
+/// * `ics` - The (period, IoC) pairs, as returned by `get_periodic_ics`
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_periods;
+/// use cipher_identifier::statistical_tests::mic::get_periodic_ics;
+///
+/// display_periods(&get_periodic_ics("LXFOPVEFRNHRLXFOPVEFRNHR", 10));
+/// ```
+pub fn display_periods(ics: &[(usize, f64)]) {
+    let best = ics.iter().map(|(_, ic)| *ic).fold(0.0, f64::max);
+    
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Period").style_spec("Fc"),
+        Cell::new("IoC").style_spec("Fb"),
+        Cell::new("").style_spec("Fb"),
+    ]));
+    for (period, ic) in ics {
+        let bar = "#".repeat(if best > 0.0 { (ic / best * 40.0).round() as usize } else { 0 });
+        let style = if *ic == best { "Fg" } else { "" };
+        table.add_row(Row::new(vec![
+            Cell::new(&period.to_string()).style_spec(style),
+            Cell::new(&format!("{:.3}", ic)).style_spec(style),
+            Cell::new(&bar).style_spec(style),
+        ]));
+    }
+    
+    println!("\n{}", "Periodic index of coincidence".bold());
+    print_table(&table);
+}
+
+/// Displays the letter frequency chart, the periodic IoC plot and the digraph heatmap
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext to chart
+/// * `style` - The characters to draw the charts with
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::charts::ChartStyle;
+/// use cipher_identifier::display::display_charts;
+///
+/// display_charts("LXFOPVEFRNHRLXFOPVEFRNHR", ChartStyle::Ascii);
+/// ```
+pub fn display_charts(text: &str, style: ChartStyle) {
+    println!("\n{}", "Letter frequencies (bars) against English (marker)".bold());
+    println!("{}", charts::frequency_chart(text, style));
+    
+    println!("\n{}", "Periodic index of coincidence".bold());
+    println!("{}", charts::periodic_ioc_chart(&get_periodic_ics(text, MAX_PERIOD), style));
+    
+    println!("\n{}", "Digraph frequencies (row: first letter, column: second letter)".bold());
+    println!("{}", charts::digraph_heatmap(text, style));
+}
+
+/// Displays the Kasiski examination, highlighting the most common factor
+///
+/// # Arguments
+///
+/// * `factors` - The (factor, count) pairs, as returned by `get_kasiski_factors`
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_kasiski;
+/// use cipher_identifier::statistical_tests::kasiski::get_kasiski_factors;
+///
+/// display_kasiski(&get_kasiski_factors("LXFOPVEFRNHRLXFOPVEFRNHR", 10));
+/// ```
+pub fn display_kasiski(factors: &[(usize, usize)]) {
+    let best = factors.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Factor").style_spec("Fc"),
+        Cell::new("Repeats").style_spec("Fb"),
+    ]));
+    for (factor, count) in factors {
+        let style = if best > 0 && *count == best { "Fg" } else { "" };
+        table.add_row(Row::new(vec![
+            Cell::new(&factor.to_string()).style_spec(style),
+            Cell::new(&count.to_string()).style_spec(style),
+        ]));
+    }
+    
+    println!("\n{}", "Kasiski examination (repeated trigram distances per factor)".bold());
+    print_table(&table);
+}
+
+/// Displays the signed z-score of each statistical test for each of the given ciphers
+///
+/// The absolute values in a row sum to the cipher's score.
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext
+/// * `ciphers` - The ciphers to break down, usually the ranked ones
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::display_z_scores;
+///
+/// display_z_scores("LXFOPVEFRNHRLXFOPVEFRNHR", &["Vigenere", "playfair"]);
+/// ```
+pub fn display_z_scores(text: &str, ciphers: &[&str]) {
+    let stats = identify_cipher::get_cipher_stats(text);
+    
+    let mut table = Table::new();
+    let mut titles = vec![Cell::new("Cipher").style_spec("Fc")];
+    titles.extend(identify_cipher::STAT_NAMES.iter().map(|name| Cell::new(name).style_spec("Fb")));
+    table.set_titles(Row::new(titles));
+    
+    for cipher in ciphers {
+        let Some(z_scores) = identify_cipher::get_stat_z_scores(&stats, cipher) else {
+            continue;
+        };
+        let mut row = vec![Cell::new(cipher)];
+        row.extend(z_scores.iter().map(|z| Cell::new(&format!("{:+.2}", z))));
+        table.add_row(Row::new(row));
+    }
+    
+    println!("\n{}", "Z-score per statistical test".bold());
+    print_table(&table);
+}
+
+/// Prints a summary of the given cipher types
+pub(crate) fn display_catalog(catalog: &CipherCatalog, names: &[&str], format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    let entries: Vec<CatalogEntry> = names
+        .iter()
+        .filter_map(|name| catalog.get(name).map(|cipher_type| CatalogEntry { name, cipher_type }))
+        .collect();
+    
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            return Ok(());
+        }
+        OutputFormat::Ndjson => {
+            for entry in &entries {
+                println!("{}", serde_json::to_string(entry)?);
+            }
+            return Ok(());
+        }
+        OutputFormat::Csv => return print_catalog_csv(&entries),
+        OutputFormat::Table => {}
+    }
+    
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Cipher").style_spec("Fc"),
+        Cell::new("Type").style_spec("Fg"),
+        Cell::new("Subtype").style_spec("Fg"),
+        Cell::new("Table").style_spec("Fb"),
+        Cell::new("Size").style_spec("Fb"),
+    ]));
+    
+    for entry in &entries {
+        table.add_row(Row::new(vec![
+            Cell::new(entry.name),
+            Cell::new(&entry.cipher_type.types.join(", ")),
+            Cell::new(&entry.cipher_type.subtypes.iter().chain(&entry.cipher_type.subtypes2).cloned().collect::<Vec<_>>().join(", ")),
+            Cell::new(&entry.cipher_type.table.join(", ")),
+            Cell::new(&entry.cipher_type.size),
+        ]));
+    }
+    
+    println!("{}", format!("{} supported ciphers", entries.len()).bold());
+    print_table(&table);
+    Ok(())
+}
+
+/// Prints all metadata of a cipher type
+pub(crate) fn describe_cipher(name: &str, cipher_type: &CipherType, format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&CatalogEntry { name, cipher_type })?);
+            return Ok(());
+        }
+        OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string(&CatalogEntry { name, cipher_type })?);
+            return Ok(());
+        }
+        OutputFormat::Csv => return print_catalog_csv(&[CatalogEntry { name, cipher_type }]),
+        OutputFormat::Table => {}
+    }
+    
+    let mut table = Table::new();
+    let fields = [
+        ("Aliases", cipher_type.aliases.join(", ")),
+        ("Types", cipher_type.types.join(", ")),
+        ("Subtypes", cipher_type.subtypes.join(", ")),
+        ("Subtypes (tertiary)", cipher_type.subtypes2.join(", ")),
+        ("Table", cipher_type.table.join(", ")),
+        ("Size", cipher_type.size.clone()),
+        ("Constraints", serde_json::to_string(&cipher_type.constraints)?),
+        ("Notes", cipher_type.notes.clone()),
+    ];
+    for (field, value) in fields {
+        table.add_row(Row::new(vec![Cell::new(field).style_spec("Fc"), Cell::new(&value)]));
+    }
+    
+    println!("{}", name.bold());
+    print_table(&table);
+    Ok(())
+}
+
+/// Finds letters that are missing from the ciphertext
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext to analyze
+///
+/// # Returns
+///
+/// A string containing the missing letters
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::display::find_missing_letters;
+///
+/// let missing = find_missing_letters("HELLOWORLD");
+/// assert!(missing.contains('A'));
+/// assert!(!missing.contains('L'));
+/// ```
+pub fn find_missing_letters(text: &str) -> String {
+    let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let text_chars: HashSet<char> = text.chars().collect();
+    
+    alphabet
+        .chars()
+        .filter(|&c| !text_chars.contains(&c))
+        .collect()
+}
+
+/// Prints cipher types as CSV, joining list fields with semicolons
+fn print_catalog_csv(entries: &[CatalogEntry]) -> Result<(), Box<dyn Error>> {
+    let mut writer = prettytable::csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["name", "aliases", "types", "subtypes", "subtypes2", "table", "size", "notes"])?;
+    for entry in entries {
+        let cipher_type = entry.cipher_type;
+        writer.write_record([
+            entry.name,
+            &cipher_type.aliases.join(";"),
+            &cipher_type.types.join(";"),
+            &cipher_type.subtypes.join(";"),
+            &cipher_type.subtypes2.join(";"),
+            &cipher_type.table.join(";"),
+            &cipher_type.size,
+            &cipher_type.notes,
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns the heading of the ranking table
+pub(crate) fn ranking_title(number: usize, rejected: bool) -> String {
+    if rejected {
+        format!("Nearest {} candidates", number)
+    } else {
+        format!("Top {} most likely ciphers", number)
+    }
+}
@@ -0,0 +1,159 @@
+//! Hierarchy Module
+//!
+//! This module aggregates per-cipher probabilities into probabilities per cipher family
+//! (`CipherType::types`, e.g. substitution) and subtype (`CipherType::subtypes`, e.g.
+//! polyalphabetic). A family is often identified with much more confidence than the exact
+//! cipher, because the ciphers within it have similar statistics.
+//!
+//! The probability of a cipher listed under several groups is split evenly between them, so
+//! the probabilities of one level never sum to more than 1. Ciphers without a subtype (most
+//! transpositions) are left out of the subtype level, whose probabilities can sum to less.
+
+use serde::Serialize;
+
+use crate::calibration::{self, CalibratedIdentification, Calibration};
+use crate::constraints::{self, Violation};
+use crate::identify_cipher;
+use crate::priors::Priors;
+use crate::models::catalog::CipherCatalog;
+use crate::models::cipher_type::CipherType;
+
+/// The aggregated probability of a group of ciphers
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupProbability {
+    /// Name of the family or subtype
+    pub name: String,
+
+    /// Total probability of the ciphers in the group
+    pub probability: f64,
+}
+
+/// A two-stage identification: families and subtypes first, then the specific ciphers
+#[derive(Debug, Clone, Serialize)]
+pub struct HierarchicalIdentification {
+    /// Probability per cipher family, most likely first
+    pub families: Vec<GroupProbability>,
+
+    /// Probability per cipher subtype, most likely first
+    pub subtypes: Vec<GroupProbability>,
+
+    /// The per-cipher ranking
+    pub ciphers: CalibratedIdentification,
+
+    /// Constraints of the candidate ciphers that the text violates
+    pub violations: Vec<Violation>,
+}
+
+/// Sums cipher probabilities per group
+///
+/// A cipher in several groups contributes an equal share of its probability to each of them.
+///
+/// # Arguments
+///
+/// * `probabilities` - The (cipher_type, probability) pairs
+/// * `catalog` - Metadata of the cipher types
+/// * `groups` - Selects the groups a cipher belongs to, e.g. `|c| &c.types`
+///
+/// # Returns
+///
+/// The probability of each group, most likely first; ciphers without metadata are ignored
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::hierarchy::aggregate;
+/// use cipher_identifier::models::catalog::CipherCatalog;
+///
+/// let catalog = CipherCatalog::bundled();
+/// let probabilities = vec![("Vigenere".to_string(), 0.3), ("Beaufort".to_string(), 0.3), ("columnar".to_string(), 0.4)];
+/// let families = aggregate(&probabilities, &catalog, |c| &c.types);
+/// assert_eq!(families[0].name, "substitution");
+/// assert!((families[0].probability - 0.6).abs() < 1e-9);
+/// ```
+pub fn aggregate<F>(probabilities: &[(String, f64)], catalog: &CipherCatalog, groups: F) -> Vec<GroupProbability>
+where
+    F: Fn(&CipherType) -> &Vec<String>,
+{
+    let mut totals: Vec<GroupProbability> = Vec::new();
+    for (cipher, probability) in probabilities {
+        let Some(cipher_type) = catalog.get(cipher) else {
+            continue;
+        };
+        let groups = groups(cipher_type);
+        let share = probability / groups.len() as f64;
+        for group in groups {
+            match totals.iter_mut().find(|g| &g.name == group) {
+                Some(total) => total.probability += share,
+                None => totals.push(GroupProbability { name: group.clone(), probability: share }),
+            }
+        }
+    }
+
+    totals.sort_by(|a, b| {
+        b.probability
+            .partial_cmp(&a.probability)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    totals
+}
+
+/// Identifies the cipher family and subtype of a text, along with the most likely ciphers
+///
+/// Ciphers whose constraints the text violates are ruled out or penalized before the
+/// probabilities are computed.
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext to analyze
+/// * `candidates` - The cipher types to consider
+/// * `priors` - Prior weights of the cipher types
+/// * `num_results` - The number of top ciphers to return
+/// * `highlight_cipher` - Optional cipher type to move to the top of the ciphers
+/// * `calibration` - The calibration used to turn scores into probabilities
+/// * `catalog` - Metadata of the cipher types
+///
+/// # Returns
+///
+/// The family, subtype and cipher probabilities
+pub fn identify_hierarchical(
+    text: &str,
+    candidates: &[String],
+    priors: &Priors,
+    num_results: usize,
+    highlight_cipher: Option<&str>,
+    calibration: &Calibration,
+    catalog: &CipherCatalog,
+) -> HierarchicalIdentification {
+    let stats = identify_cipher::get_cipher_stats(text);
+    let scores = priors.adjust(identify_cipher::get_cipher(&stats, candidates), calibration.temperature);
+    let (scores, violations) = constraints::apply_constraints(text, scores, catalog, calibration.temperature);
+    let probabilities: Vec<(String, f64)> = scores
+        .iter()
+        .map(|(c, _)| c.clone())
+        .zip(calibration.probabilities(&scores))
+        .collect();
+
+    HierarchicalIdentification {
+        families: aggregate(&probabilities, catalog, |c| &c.types),
+        subtypes: aggregate(&probabilities, catalog, |c| &c.subtypes),
+        ciphers: calibration::calibrate_scores(text, &stats, scores, num_results, highlight_cipher, calibration),
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cipher_in_several_families_is_split_between_them() {
+        let catalog = CipherCatalog::from_json(r#"{"pollux": {"types": ["substitution", "transposition"]}}"#).unwrap();
+        let probabilities = vec![("pollux".to_string(), 0.5), ("unlisted".to_string(), 0.5)];
+
+        let families = aggregate(&probabilities, &catalog, |c| &c.types);
+        assert_eq!(families.len(), 2);
+        assert!(families.iter().all(|f| f.probability == 0.25));
+        assert!(aggregate(&probabilities, &catalog, |c| &c.subtypes).is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! Input Module
+//!
+//! This module gathers the ciphertexts to analyze from the command line, files, glob patterns
+//! and standard input.
+//!
+//! Without batch mode, each file (or standard input) holds a single ciphertext. In batch mode,
+//! each non-empty line is a ciphertext of its own, given either as plain text or as a JSON
+//! object with a `ciphertext` field, as in the benchmark corpora.
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+/// The path that stands for standard input
+pub const STDIN_PATH: &str = "-";
+
+/// A ciphertext to analyze, with where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    /// The file, `-` for standard input or `text` for `--text`, with the line number in batch mode
+    pub source: String,
+
+    /// The ciphertext, without whitespace and in uppercase
+    pub text: String,
+}
+
+/// Removes whitespace from a ciphertext and converts it to uppercase
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::input::preprocess;
+///
+/// assert_eq!(preprocess("hello world\n"), "HELLOWORLD");
+/// ```
+pub fn preprocess(text: &str) -> String {
+    let whitespace = Regex::new(r"\s+").expect("valid regex");
+    whitespace.replace_all(text, "").to_uppercase()
+}
+
+/// Expands glob patterns into the files they match
+///
+/// Paths without glob metacharacters, and `-`, are kept as they are, so that a missing file is
+/// reported when it is opened.
+///
+/// # Arguments
+///
+/// * `patterns` - Paths or glob patterns such as `puzzles/*.txt`
+///
+/// # Returns
+///
+/// The matching paths in the order of the patterns, each pattern's matches sorted, or an error
+/// if a pattern is invalid or matches nothing
+pub fn expand_paths(patterns: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let pattern_str = pattern.to_string_lossy();
+        if !pattern_str.contains(['*', '?', '[']) {
+            paths.push(pattern.clone());
+            continue;
+        }
+
+        let mut matches: Vec<PathBuf> = glob::glob(&pattern_str)?.collect::<Result<_, _>>()?;
+        if matches.is_empty() {
+            return Err(format!("No file matches '{}'", pattern_str).into());
+        }
+        matches.sort();
+        paths.extend(matches);
+    }
+    Ok(paths)
+}
+
+/// Splits the content of a batch file into one input per non-empty line
+///
+/// # Arguments
+///
+/// * `content` - The content of the batch file
+/// * `source` - Name of the batch file, used in the source of each input and in errors
+///
+/// # Returns
+///
+/// The inputs, or an error if a JSON line has no `ciphertext` field
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::input::split_batch;
+///
+/// let content = "LXFOPVEFRNHR\n\n{\"ciphertype\": \"Vigenere\", \"ciphertext\": \"abc def\"}\n";
+/// let inputs = split_batch(content, "puzzles.txt").unwrap();
+/// assert_eq!(inputs.len(), 2);
+/// assert_eq!(inputs[1].source, "puzzles.txt:3");
+/// assert_eq!(inputs[1].text, "ABCDEF");
+/// ```
+pub fn split_batch(content: &str, source: &str) -> Result<Vec<Input>, Box<dyn Error>> {
+    let mut inputs = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let text = if line.starts_with('{') {
+            let value: serde_json::Value =
+                serde_json::from_str(line).map_err(|e| format!("{}:{}: {}", source, i + 1, e))?;
+            value["ciphertext"]
+                .as_str()
+                .ok_or_else(|| format!("{}:{}: expected a `ciphertext` field", source, i + 1))?
+                .to_string()
+        } else {
+            line.to_string()
+        };
+        inputs.push(Input { source: format!("{}:{}", source, i + 1), text: preprocess(&text) });
+    }
+    Ok(inputs)
+}
+
+/// Gathers the ciphertexts to analyze
+///
+/// Standard input is read if a file is `-`, or if neither text nor files are given and
+/// standard input is not a terminal.
+///
+/// # Arguments
+///
+/// * `text` - The ciphertext given on the command line
+/// * `files` - Paths, glob patterns or `-`
+/// * `batch` - Whether each line of a file is a separate ciphertext
+///
+/// # Returns
+///
+/// The inputs in order, or an error if there are none or a file cannot be read
+pub fn read_inputs(text: Option<String>, files: &[PathBuf], batch: bool) -> Result<Vec<Input>, Box<dyn Error>> {
+    let mut sources: Vec<(String, String)> = Vec::new();
+    if let Some(text) = text {
+        sources.push(("text".to_string(), text));
+    }
+    for path in expand_paths(files)? {
+        sources.push((path.display().to_string(), read_source(&path)?));
+    }
+    if sources.is_empty() && !io::stdin().is_terminal() {
+        sources.push((STDIN_PATH.to_string(), read_source(Path::new(STDIN_PATH))?));
+    }
+    if sources.is_empty() {
+        return Err("Text input expected. Run with --help for usage information.".into());
+    }
+
+    let mut inputs = Vec::new();
+    for (source, content) in sources {
+        if batch {
+            inputs.extend(split_batch(&content, &source)?);
+        } else {
+            inputs.push(Input { text: preprocess(&content), source });
+        }
+    }
+    if inputs.is_empty() {
+        return Err("The batch input contains no ciphertext".into());
+    }
+    Ok(inputs)
+}
+
+/// Reads a file, or standard input for `-`
+fn read_source(path: &Path) -> Result<String, Box<dyn Error>> {
+    if path == Path::new(STDIN_PATH) {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        Ok(content)
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_globs_expand_to_sorted_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.txt", "a.txt", "c.json"] {
+            fs::write(dir.path().join(name), "LXFOPVEFRNHR").unwrap();
+        }
+
+        let pattern = dir.path().join("*.txt");
+        let paths = expand_paths(&[pattern, PathBuf::from("-")]).unwrap();
+        assert_eq!(paths, vec![dir.path().join("a.txt"), dir.path().join("b.txt"), PathBuf::from("-")]);
+        assert!(expand_paths(&[dir.path().join("*.csv")]).is_err());
+    }
+
+    #[test]
+    fn test_batch_line_without_ciphertext_is_an_error() {
+        let error = split_batch("ABC\n{\"ciphertype\": \"Vigenere\"}", "batch.jsonl").unwrap_err();
+        assert_eq!(error.to_string(), "batch.jsonl:2: expected a `ciphertext` field");
+    }
+}
@@ -9,6 +9,25 @@ pub mod identify_cipher;
 pub mod statistical_tests;
 pub mod models;
 pub mod benchmark;
+pub mod ablation;
+pub mod analysis;
+pub mod calibration;
+pub mod charts;
+pub mod classifiers;
+pub mod color;
+pub mod commands;
+pub mod compare;
+pub mod config;
+pub mod constraints;
+pub mod crossval;
+pub mod display;
+pub mod hierarchy;
+pub mod input;
+pub mod priors;
+pub mod repl;
+pub mod report;
+pub mod similarity;
+pub mod watch;
 
 /// Re-export main types for convenience
 pub use cipher_analyzer::{CipherAnalyzer, CliArgs};
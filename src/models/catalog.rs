@@ -0,0 +1,380 @@
+//! Cipher Catalog
+//!
+//! This module provides the metadata of the supported cipher types. The metadata from
+//! `resources/cipher_types.json` is bundled into the binary, so it is available wherever the
+//! binary is run; a different file can be used instead by passing its path or by setting the
+//! `CIPHER_IDENTIFIER_CATALOG` environment variable.
+
+use std::error::Error;
+use std::path::Path;
+
+use crate::models::cipher_type::{CipherType, CipherTypes, load_cipher_types};
+
+/// Cipher metadata bundled into the binary at compile time
+const BUNDLED_CIPHER_TYPES: &str = include_str!("../../resources/cipher_types.json");
+
+/// Environment variable holding the path of a cipher metadata file to use instead of the bundled one
+pub const CATALOG_ENV_VAR: &str = "CIPHER_IDENTIFIER_CATALOG";
+
+/// The metadata of all supported cipher types
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::models::catalog::CipherCatalog;
+///
+/// let catalog = CipherCatalog::bundled();
+/// assert_eq!(catalog.primary_type("columnar"), "transposition");
+/// assert!(catalog.family("polyalphabetic").contains(&"Vigenere"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CipherCatalog {
+    cipher_types: CipherTypes,
+}
+
+impl CipherCatalog {
+    /// Returns the cipher metadata bundled into the binary
+    pub fn bundled() -> Self {
+        Self::from_json(BUNDLED_CIPHER_TYPES).expect("bundled cipher metadata is valid JSON")
+    }
+
+    /// Parses cipher metadata in the format of `resources/cipher_types.json`
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(CipherCatalog { cipher_types: serde_json::from_str(json)? })
+    }
+
+    /// Loads cipher metadata from a JSON file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let path_str = path.to_str().ok_or("Cipher metadata path is not valid UTF-8")?;
+        let cipher_types = load_cipher_types(path_str)
+            .map_err(|e| format!("Failed to load cipher metadata from {}: {}", path.display(), e))?;
+        Ok(CipherCatalog { cipher_types })
+    }
+
+    /// Loads the catalog from the override path, the environment variable or the bundled metadata
+    ///
+    /// # Arguments
+    ///
+    /// * `override_path` - Optional path of a cipher metadata file, e.g. from a command line flag
+    ///
+    /// # Returns
+    ///
+    /// The catalog from `override_path` if given, otherwise from the file named by
+    /// `CIPHER_IDENTIFIER_CATALOG` if set, otherwise the bundled catalog
+    pub fn load(override_path: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        if let Some(path) = override_path {
+            return Self::from_path(path);
+        }
+        match std::env::var_os(CATALOG_ENV_VAR) {
+            Some(path) if !path.is_empty() => Self::from_path(path),
+            _ => Ok(Self::bundled()),
+        }
+    }
+
+    /// Returns the underlying cipher types indexed by name
+    pub fn cipher_types(&self) -> &CipherTypes {
+        &self.cipher_types
+    }
+
+    /// Returns the names of all cipher types in alphabetical order
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.cipher_types.keys().map(|k| k.as_str()).collect();
+        names.sort_by_key(|n| n.to_lowercase());
+        names
+    }
+
+    /// Returns the metadata of a cipher type by its exact name
+    pub fn get(&self, name: &str) -> Option<&CipherType> {
+        self.cipher_types.get(name)
+    }
+
+    /// Finds a cipher type by name or alias
+    ///
+    /// Names are compared after `normalize_name`, so case, accents, spaces and punctuation
+    /// are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name or one of the `aliases` of a cipher type
+    ///
+    /// # Returns
+    ///
+    /// The canonical name and metadata of the cipher type, if found
+    pub fn lookup(&self, name: &str) -> Option<(&str, &CipherType)> {
+        if let Some((key, cipher_type)) = self.cipher_types.get_key_value(name) {
+            return Some((key.as_str(), cipher_type));
+        }
+        let normalized = normalize_name(name);
+        self.cipher_types
+            .iter()
+            .find(|(key, cipher_type)| {
+                normalize_name(key) == normalized || cipher_type.aliases.iter().any(|a| normalize_name(a) == normalized)
+            })
+            .map(|(key, cipher_type)| (key.as_str(), cipher_type))
+    }
+
+    /// Resolves a name or alias to the canonical cipher name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name or one of the `aliases` of a cipher type
+    ///
+    /// # Returns
+    ///
+    /// The canonical name, or an error listing similar names if the cipher is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::models::catalog::CipherCatalog;
+    ///
+    /// let catalog = CipherCatalog::bundled();
+    /// assert_eq!(catalog.resolve("aristocrat").unwrap(), "simplesubstitution");
+    /// assert!(catalog.resolve("vigenre").unwrap_err().to_string().contains("Vigenere"));
+    /// ```
+    pub fn resolve(&self, name: &str) -> Result<&str, Box<dyn Error>> {
+        if let Some((canonical, _)) = self.lookup(name) {
+            return Ok(canonical);
+        }
+        let suggestions = self.suggest(name);
+        if suggestions.is_empty() {
+            Err(format!("Unknown cipher '{}'. Run `list` to see the supported ciphers", name).into())
+        } else {
+            Err(format!("Unknown cipher '{}'. Did you mean: {}?", name, suggestions.join(", ")).into())
+        }
+    }
+
+    /// Returns up to three known cipher names similar to an unknown name
+    ///
+    /// A cipher is similar if its name or an alias contains the given name or is within a
+    /// small edit distance of it.
+    pub fn suggest(&self, name: &str) -> Vec<&str> {
+        let normalized = normalize_name(name);
+        if normalized.is_empty() {
+            return Vec::new();
+        }
+        let max_distance = (normalized.chars().count() / 3).max(2);
+
+        let mut candidates: Vec<(usize, &str)> = Vec::new();
+        for canonical in self.names() {
+            let cipher_type = &self.cipher_types[canonical];
+            let distance = std::iter::once(canonical)
+                .chain(cipher_type.aliases.iter().map(|a| a.as_str()))
+                .map(|candidate| {
+                    let candidate = normalize_name(candidate);
+                    if candidate.contains(&normalized) { 0 } else { edit_distance(&normalized, &candidate) }
+                })
+                .min()
+                .unwrap_or(usize::MAX);
+            if distance <= max_distance {
+                candidates.push((distance, canonical));
+            }
+        }
+
+        candidates.sort_by_key(|(distance, _)| *distance);
+        candidates.into_iter().take(3).map(|(_, name)| name).collect()
+    }
+
+    /// Returns the names of the cipher types in a family, in alphabetical order
+    ///
+    /// # Arguments
+    ///
+    /// * `family` - A type, subtype or tertiary subtype, e.g. "transposition", "polygraphic" or "fractionation"
+    pub fn family(&self, family: &str) -> Vec<&str> {
+        self.names()
+            .into_iter()
+            .filter(|name| {
+                let cipher_type = &self.cipher_types[*name];
+                cipher_type
+                    .types
+                    .iter()
+                    .chain(&cipher_type.subtypes)
+                    .chain(&cipher_type.subtypes2)
+                    .any(|f| f.eq_ignore_ascii_case(family))
+            })
+            .collect()
+    }
+
+    /// Returns the names of the cipher types that use a table, in alphabetical order
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The kind of table, e.g. "polybius square" or "tabula recta"
+    pub fn with_table(&self, table: &str) -> Vec<&str> {
+        self.names()
+            .into_iter()
+            .filter(|name| self.cipher_types[*name].table.iter().any(|t| t.eq_ignore_ascii_case(table)))
+            .collect()
+    }
+
+    /// Returns the primary type of a cipher, or "unknown" if it is not in the catalog
+    pub fn primary_type(&self, cipher: &str) -> String {
+        self.lookup(cipher)
+            .and_then(|(_, ct)| ct.types.first())
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// Normalizes a cipher name for comparison
+///
+/// Letters are lowercased and stripped of accents, and everything except letters and digits
+/// is removed, so "Vigenère", "vigenere" and "VIGENERE" all compare equal.
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::models::catalog::normalize_name;
+///
+/// assert_eq!(normalize_name("Vigenère"), "vigenere");
+/// assert_eq!(normalize_name("Quagmire I"), "quagmirei");
+/// assert_eq!(normalize_name("monome-dinome"), "monomedinome");
+/// ```
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            _ => c,
+        })
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Computes the Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Restricts the cipher types considered during identification
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::identify_cipher::cipher_type_names;
+/// use cipher_identifier::models::catalog::{CandidateFilter, CipherCatalog};
+///
+/// let filter = CandidateFilter {
+///     families: vec!["transposition".to_string()],
+///     exclude: vec!["columnar".to_string()],
+///     ..Default::default()
+/// };
+/// let candidates = filter.apply(&CipherCatalog::bundled(), cipher_type_names()).unwrap();
+/// assert!(candidates.contains(&"amsco".to_string()));
+/// assert!(!candidates.contains(&"columnar".to_string()));
+/// assert!(!candidates.contains(&"Vigenere".to_string()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CandidateFilter {
+    /// If not empty, only these ciphers (names or aliases) are considered
+    pub only: Vec<String>,
+
+    /// Ciphers (names or aliases) that are never considered
+    pub exclude: Vec<String>,
+
+    /// If not empty, only ciphers in one of these families or using one of these tables are considered
+    pub families: Vec<String>,
+}
+
+impl CandidateFilter {
+    /// Applies the filter to a list of cipher names
+    ///
+    /// # Arguments
+    ///
+    /// * `catalog` - Metadata used to resolve names, families and tables
+    /// * `names` - The canonical names to filter, e.g. `identify_cipher::cipher_type_names()`
+    ///
+    /// # Returns
+    ///
+    /// The names that pass the filter, in their original order, or an error if a name or
+    /// family is unknown or no cipher is left
+    pub fn apply(&self, catalog: &CipherCatalog, mut names: Vec<String>) -> Result<Vec<String>, Box<dyn Error>> {
+        if !self.only.is_empty() {
+            let only = self.only.iter().map(|n| catalog.resolve(n)).collect::<Result<Vec<&str>, _>>()?;
+            names.retain(|name| only.contains(&name.as_str()));
+        }
+
+        if !self.families.is_empty() {
+            let mut members = Vec::new();
+            for family in &self.families {
+                let mut found = catalog.family(family);
+                found.extend(catalog.with_table(family));
+                if found.is_empty() {
+                    return Err(format!("Unknown cipher family or table '{}'", family).into());
+                }
+                members.extend(found);
+            }
+            names.retain(|name| members.contains(&name.as_str()));
+        }
+
+        let exclude = self.exclude.iter().map(|n| catalog.resolve(n)).collect::<Result<Vec<&str>, _>>()?;
+        names.retain(|name| !exclude.contains(&name.as_str()));
+
+        if names.is_empty() {
+            return Err("No cipher is left to consider after applying the filters".into());
+        }
+        Ok(names)
+    }
+}
+
+impl Default for CipherCatalog {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identify_cipher::CIPHER_TYPES;
+
+    #[test]
+    fn test_bundled_catalog_covers_all_cipher_types() {
+        let catalog = CipherCatalog::bundled();
+        for cipher in CIPHER_TYPES {
+            assert!(catalog.get(cipher).is_some(), "{} is missing from the catalog", cipher);
+        }
+        assert_eq!(catalog.lookup("VIGENERE").map(|(name, _)| name), Some("Vigenere"));
+        assert!(catalog.with_table("Polybius Square").contains(&"playfair"));
+    }
+
+    #[test]
+    fn test_names_and_aliases_are_unambiguous() {
+        let catalog = CipherCatalog::bundled();
+        let mut seen = std::collections::HashSet::new();
+        for name in catalog.names() {
+            for alias in std::iter::once(name).chain(catalog.get(name).unwrap().aliases.iter().map(|a| a.as_str())) {
+                assert_eq!(catalog.resolve(alias).unwrap(), name);
+                assert!(seen.insert(normalize_name(alias)), "{} is ambiguous", alias);
+            }
+        }
+        assert_eq!(catalog.resolve("QUAGMIRE 3").unwrap(), "Quagmire III");
+    }
+
+    #[test]
+    fn test_load_override_path() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, br#"{"custom": {"types": ["transposition"], "aliases": ["mine"]}}"#).unwrap();
+
+        let catalog = CipherCatalog::load(Some(file.path())).unwrap();
+        assert_eq!(catalog.names(), vec!["custom"]);
+        assert_eq!(catalog.lookup("Mine").map(|(name, _)| name), Some("custom"));
+    }
+}
@@ -19,6 +19,8 @@ use std::collections::HashMap;
 ///     table: vec!["polybius square".to_string()],
 ///     size: "6x6".to_string(),
 ///     notes: "The 6x6 Bifid cipher is a variant of the Bifid cipher, which uses a 6x6 grid instead of a 5x5 grid.".to_string(),
+///     aliases: vec![],
+///     constraints: Default::default(),
 /// };
 ///
 /// assert_eq!(cipher_type.types[0], "substitution");
@@ -48,6 +50,52 @@ pub struct CipherType {
     /// Additional notes about the cipher
     #[serde(default)]
     pub notes: String,
+    
+    /// Other names the cipher is known by
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    
+    /// Properties every ciphertext of this cipher has
+    #[serde(default)]
+    pub constraints: CipherConstraints,
+}
+
+/// Machine-readable properties of the ciphertexts a cipher can produce
+///
+/// Unset fields are not checked.
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::models::cipher_type::CipherConstraints;
+///
+/// let playfair: CipherConstraints = serde_json::from_str(
+///     r#"{"symbols": "ABCDEFGHIKLMNOPQRSTUVWXYZ", "length_multiple": 2, "doubled_letters": false}"#,
+/// ).unwrap();
+/// assert_eq!(playfair.length_multiple, Some(2));
+/// assert!(!playfair.digits_only);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CipherConstraints {
+    /// The symbols the ciphertext can contain, e.g. "ABCDEFGHIKLMNOPQRSTUVWXYZ" for a 5x5 square
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbols: Option<String>,
+    
+    /// The maximum number of different symbols in the ciphertext
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alphabet_size: Option<usize>,
+    
+    /// The ciphertext length is always a multiple of this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length_multiple: Option<usize>,
+    
+    /// Whether a ciphertext pair (positions 2k and 2k+1) can be a doubled letter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doubled_letters: Option<bool>,
+    
+    /// Whether the ciphertext consists of digits only
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub digits_only: bool,
 }
 
 /// A collection of cipher types indexed by name
@@ -2,4 +2,5 @@
 //!
 //! This module contains data structures used throughout the application.
 
-pub mod cipher_type;
\ No newline at end of file
+pub mod catalog;
+pub mod cipher_type;
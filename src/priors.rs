@@ -0,0 +1,199 @@
+//! Priors Module
+//!
+//! This module provides prior weights per cipher type, so that ciphers that are common in a
+//! given context (e.g. the Aristocrat in ACA contests) are preferred when the statistics
+//! cannot tell the candidates apart.
+//!
+//! Scores are treated as negative log likelihoods scaled by the calibration temperature, so a
+//! prior weight `w` lowers the score of a cipher by `temperature * ln(w / mean weight)`. With
+//! uniform weights the scores are unchanged.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::identify_cipher::CipherScore;
+use crate::models::catalog::CipherCatalog;
+
+/// Names of the built-in presets
+pub const PRESETS: [&str; 3] = ["uniform", "aca", "ctf"];
+
+/// Relative frequencies of ciphers in ACA contests: mostly Aristocrats and Patristocrats,
+/// with the other types spread over the Cipher Exchange section
+const ACA_WEIGHTS: [(&str, f64); 2] = [("simplesubstitution", 20.0), ("Patristocrat", 8.0)];
+
+/// Relative frequencies of classical ciphers in CTF challenges
+const CTF_WEIGHTS: [(&str, f64); 10] = [
+    ("Vigenere", 10.0),
+    ("simplesubstitution", 10.0),
+    ("columnar", 5.0),
+    ("playfair", 5.0),
+    ("redefence", 4.0),
+    ("Beaufort", 3.0),
+    ("Autokey", 3.0),
+    ("bifid", 3.0),
+    ("foursquare", 2.0),
+    ("trifid", 2.0),
+];
+
+/// Prior weights per cipher type; ciphers without a weight have weight 1
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::priors::Priors;
+///
+/// let priors = Priors::preset("ctf").unwrap();
+/// let scores = vec![("Vigenere".to_string(), 10.0), ("syllabary".to_string(), 10.0)];
+/// let adjusted = priors.adjust(scores, 1.0);
+/// assert!(adjusted[0].1 < adjusted[1].1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Priors {
+    weights: HashMap<String, f64>,
+}
+
+impl Priors {
+    /// Returns priors that treat all ciphers as equally likely
+    pub fn uniform() -> Self {
+        Priors::default()
+    }
+
+    /// Returns one of the built-in presets
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - "uniform", "aca" (ACA contests) or "ctf" (CTF challenges)
+    pub fn preset(name: &str) -> Result<Self, Box<dyn Error>> {
+        let weights: &[(&str, f64)] = match name.to_lowercase().as_str() {
+            "uniform" => &[],
+            "aca" => &ACA_WEIGHTS,
+            "ctf" => &CTF_WEIGHTS,
+            _ => return Err(format!("Unknown priors preset '{}'. Expected one of: {}", name, PRESETS.join(", ")).into()),
+        };
+        Ok(Priors { weights: weights.iter().map(|(c, w)| (c.to_string(), *w)).collect() })
+    }
+
+    /// Creates priors from weights per cipher name or alias
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - Non-negative weight per cipher; a weight of 0 rules the cipher out
+    /// * `catalog` - Metadata used to resolve the cipher names
+    pub fn from_weights(weights: HashMap<String, f64>, catalog: &CipherCatalog) -> Result<Self, Box<dyn Error>> {
+        let mut resolved = HashMap::new();
+        for (name, weight) in weights {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!("Prior weight of '{}' must be a non-negative number", name).into());
+            }
+            resolved.insert(catalog.resolve(&name)?.to_string(), weight);
+        }
+        Ok(Priors { weights: resolved })
+    }
+
+    /// Loads priors from a JSON file mapping cipher names to weights
+    pub fn load<P: AsRef<Path>>(path: P, catalog: &CipherCatalog) -> Result<Self, Box<dyn Error>> {
+        let file = File::open(path)?;
+        let weights: HashMap<String, f64> = serde_json::from_reader(BufReader::new(file))?;
+        Self::from_weights(weights, catalog)
+    }
+
+    /// Returns a preset if `value` names one, otherwise loads the JSON file at `value`
+    pub fn from_preset_or_file(value: &str, catalog: &CipherCatalog) -> Result<Self, Box<dyn Error>> {
+        if PRESETS.iter().any(|p| p.eq_ignore_ascii_case(value)) {
+            Self::preset(value)
+        } else {
+            Self::load(value, catalog)
+        }
+    }
+
+    /// Returns the weight of a cipher
+    pub fn weight(&self, cipher: &str) -> f64 {
+        self.weights.get(cipher).copied().unwrap_or(1.0)
+    }
+
+    /// Removes the candidate ciphers that have a weight of 0
+    ///
+    /// # Arguments
+    ///
+    /// * `candidates` - The canonical names of the ciphers to consider
+    ///
+    /// # Returns
+    ///
+    /// The candidates with a positive weight, in their original order, or an error if no
+    /// cipher is left
+    pub fn filter(&self, mut candidates: Vec<String>) -> Result<Vec<String>, Box<dyn Error>> {
+        candidates.retain(|c| self.weight(c) > 0.0);
+        if candidates.is_empty() {
+            return Err("No cipher is left to consider: every candidate has a prior weight of 0".into());
+        }
+        Ok(candidates)
+    }
+
+    /// Combines scores with the prior weights
+    ///
+    /// # Arguments
+    ///
+    /// * `scores` - The (cipher_type, score) pairs, lower is better
+    /// * `temperature` - The scale of the scores relative to negative log likelihoods
+    ///
+    /// # Returns
+    ///
+    /// The adjusted scores in the same order, without the ciphers that have a weight of 0
+    pub fn adjust(&self, scores: Vec<CipherScore>, temperature: f64) -> Vec<CipherScore> {
+        let scores: Vec<CipherScore> = scores.into_iter().filter(|(c, _)| self.weight(c) > 0.0).collect();
+        if scores.is_empty() {
+            return scores;
+        }
+        let mean = scores.iter().map(|(c, _)| self.weight(c)).sum::<f64>() / scores.len() as f64;
+        scores
+            .into_iter()
+            .map(|(cipher, score)| {
+                let adjusted = score - temperature * (self.weight(&cipher) / mean).ln();
+                (cipher, adjusted)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_priors_leave_scores_unchanged() {
+        let scores = vec![("Vigenere".to_string(), 3.0), ("bifid".to_string(), 4.0)];
+        assert_eq!(Priors::uniform().adjust(scores.clone(), 2.0), scores);
+
+        let catalog = CipherCatalog::bundled();
+        for (cipher, _) in ACA_WEIGHTS.iter().chain(&CTF_WEIGHTS) {
+            assert!(catalog.get(cipher).is_some(), "{} is not a supported cipher", cipher);
+        }
+    }
+
+    #[test]
+    fn test_zero_weight_rules_cipher_out() {
+        let catalog = CipherCatalog::bundled();
+        let weights = HashMap::from([("aristocrat".to_string(), 0.0), ("bifid".to_string(), 2.0)]);
+        let priors = Priors::from_weights(weights, &catalog).unwrap();
+
+        let scores = vec![("simplesubstitution".to_string(), 1.0), ("bifid".to_string(), 1.0), ("trifid".to_string(), 1.0)];
+        let adjusted = priors.adjust(scores, 1.0);
+        assert_eq!(adjusted.len(), 2);
+        assert!((adjusted[0].1 - (1.0 - (2.0f64 / 1.5).ln())).abs() < 1e-12);
+        assert!(Priors::preset("bogus").is_err());
+    }
+
+    #[test]
+    fn test_filter_rejects_all_zero_weights() {
+        let catalog = CipherCatalog::bundled();
+        let weights = HashMap::from([("bifid".to_string(), 0.0), ("trifid".to_string(), 0.0)]);
+        let priors = Priors::from_weights(weights, &catalog).unwrap();
+
+        let candidates = vec!["bifid".to_string(), "trifid".to_string(), "playfair".to_string()];
+        assert_eq!(priors.filter(candidates.clone()).unwrap(), vec!["playfair".to_string()]);
+        assert!(priors.filter(candidates[..2].to_vec()).is_err());
+    }
+}
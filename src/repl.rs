@@ -0,0 +1,415 @@
+//! REPL Module
+//!
+//! This module provides an interactive mode in which a ciphertext is analyzed step by step:
+//! the user pastes a ciphertext, then runs commands such as `stats`, `top 10` or
+//! `explain playfair` against it, and transforms it with `strip` and `transform` without
+//! restarting the process.
+//!
+//! Transforms are kept as a history and replayed from the original text, so they can be undone.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+
+use crate::calibration::Calibration;
+use crate::color::print_table;
+use crate::compare::compare_ciphers;
+use crate::display::{
+    display_basic_stats, display_comparison, display_explanation, display_identification, display_periods,
+    display_statistics,
+};
+use crate::hierarchy::identify_hierarchical;
+use crate::identify_cipher;
+use crate::input::preprocess;
+use crate::models::catalog::CipherCatalog;
+use crate::priors::Priors;
+use crate::report::StatsReport;
+use crate::statistical_tests::mic::get_periodic_ics;
+
+/// Longest first word of a line that is taken as a mistyped command rather than a ciphertext
+const MAX_COMMAND_LENGTH: usize = 10;
+
+/// Lines with at least this many words are taken as a ciphertext, e.g. pasted in groups of five
+const MIN_PASTED_WORDS: usize = 4;
+
+/// Help text listing the REPL commands
+const HELP: &str = "\
+Commands:
+  <ciphertext>                              Set the ciphertext (a long word or at least four words)
+  text [ciphertext]                         Show or set the ciphertext
+  stats                                     Basic stats and all statistical tests
+  top [n]                                   The n most likely ciphers (default 5)
+  explain <cipher>                          How each statistical test contributes to a cipher's score
+  compare <a>, <b>                          Compare two ciphers head-to-head
+  period [max]                              IoC of the text split into columns, for periods 1 to max (default 20)
+  freq                                      Frequency of each symbol
+  strip digits|letters|punctuation|<chars>  Remove symbols from the text
+  transform reverse|atbash|shift <n>        Transform the text
+  history                                   Show the transforms applied to the text
+  undo                                      Undo the last transform
+  reset                                     Undo all transforms
+  help                                      Show this help
+  quit                                      Leave the REPL";
+
+/// A change to the current ciphertext
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transform {
+    /// Removes `digits`, `letters`, `punctuation` or the given characters
+    Strip(String),
+
+    /// Reverses the text
+    Reverse,
+
+    /// Replaces each letter with its mirror in the alphabet (A with Z, B with Y, ...)
+    Atbash,
+
+    /// Shifts each letter forward in the alphabet
+    Shift(u8),
+}
+
+impl Transform {
+    /// Parses the arguments of a `strip` or `transform` command
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - "strip" or "transform"
+    /// * `args` - The rest of the command line
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::repl::Transform;
+    ///
+    /// assert_eq!(Transform::parse("transform", "shift 3").unwrap(), Transform::Shift(3));
+    /// assert_eq!(Transform::parse("strip", "digits").unwrap(), Transform::Strip("digits".to_string()));
+    /// assert!(Transform::parse("transform", "rot").is_err());
+    /// ```
+    pub fn parse(command: &str, args: &str) -> Result<Self, Box<dyn Error>> {
+        let args = args.trim();
+        match command {
+            "strip" if args.is_empty() => Err("Usage: strip digits|letters|punctuation|<chars>".into()),
+            "strip" => Ok(Transform::Strip(args.to_string())),
+            "transform" => {
+                let words: Vec<&str> = args.split_whitespace().collect();
+                match words.as_slice() {
+                    ["reverse"] => Ok(Transform::Reverse),
+                    ["atbash"] => Ok(Transform::Atbash),
+                    ["shift", n] => {
+                        let n: i64 = n.parse().map_err(|_| format!("Invalid shift '{}'", n))?;
+                        Ok(Transform::Shift(n.rem_euclid(26) as u8))
+                    }
+                    _ => Err("Usage: transform reverse|atbash|shift <n>".into()),
+                }
+            }
+            _ => Err(format!("'{}' is not a transform", command).into()),
+        }
+    }
+
+    /// Applies the transform to a text in uppercase
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Transform::Strip(what) => match what.as_str() {
+                "digits" => text.chars().filter(|c| !c.is_ascii_digit()).collect(),
+                "letters" => text.chars().filter(|c| !c.is_ascii_alphabetic()).collect(),
+                "punctuation" => text.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '#').collect(),
+                chars => {
+                    let chars = chars.to_uppercase();
+                    text.chars().filter(|c| !chars.contains(*c)).collect()
+                }
+            },
+            Transform::Reverse => text.chars().rev().collect(),
+            Transform::Atbash => text
+                .chars()
+                .map(|c| if c.is_ascii_uppercase() { (b'Z' - (c as u8 - b'A')) as char } else { c })
+                .collect(),
+            Transform::Shift(n) => text
+                .chars()
+                .map(|c| if c.is_ascii_uppercase() { (b'A' + (c as u8 - b'A' + n) % 26) as char } else { c })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Transform::Strip(what) => write!(f, "strip {}", what),
+            Transform::Reverse => write!(f, "transform reverse"),
+            Transform::Atbash => write!(f, "transform atbash"),
+            Transform::Shift(n) => write!(f, "transform shift {}", n),
+        }
+    }
+}
+
+/// The ciphertext of a REPL session and the transforms applied to it
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::repl::{Session, Transform};
+///
+/// let mut session = Session::new("ABC123".to_string());
+/// session.apply(Transform::Strip("digits".to_string()));
+/// session.apply(Transform::Reverse);
+/// assert_eq!(session.text(), "CBA");
+/// session.undo();
+/// assert_eq!(session.text(), "ABC");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session {
+    original: String,
+    transforms: Vec<Transform>,
+    text: String,
+}
+
+impl Session {
+    /// Starts a session on a ciphertext
+    pub fn new(text: String) -> Self {
+        Session { original: text.clone(), transforms: Vec::new(), text }
+    }
+
+    /// Returns the current text, with all transforms applied
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the transforms applied so far, oldest first
+    pub fn history(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    /// Applies a transform to the current text
+    pub fn apply(&mut self, transform: Transform) {
+        self.text = transform.apply(&self.text);
+        self.transforms.push(transform);
+    }
+
+    /// Undoes the last transform, returning it
+    pub fn undo(&mut self) -> Option<Transform> {
+        let undone = self.transforms.pop()?;
+        self.text = self.transforms.iter().fold(self.original.clone(), |text, t| t.apply(&text));
+        Some(undone)
+    }
+
+    /// Undoes all transforms
+    pub fn reset(&mut self) {
+        self.transforms.clear();
+        self.text = self.original.clone();
+    }
+}
+
+/// Runs the REPL until `quit` or the end of the input
+///
+/// # Arguments
+///
+/// * `input` - Where to read commands from, usually standard input
+/// * `initial` - The ciphertext to start with, if any
+/// * `calibration` - The calibration used for rankings and comparisons
+/// * `catalog` - Metadata of the cipher types
+///
+/// # Returns
+///
+/// Result indicating success or failure; errors of single commands are printed and do not end the REPL
+pub fn run_repl<R: BufRead>(
+    input: R,
+    initial: Option<String>,
+    calibration: &Calibration,
+    catalog: &CipherCatalog,
+) -> Result<(), Box<dyn Error>> {
+    let interactive = io::stdin().is_terminal();
+    let mut session = initial.map(Session::new);
+
+    if interactive {
+        println!("Paste a ciphertext, or type `help` for the list of commands.");
+    }
+
+    let mut lines = input.lines();
+    loop {
+        if interactive {
+            print!("{} ", ">".bold());
+            io::stdout().flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (command, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match command.to_lowercase().as_str() {
+            "quit" | "exit" => break,
+            "help" => println!("{}", HELP),
+            "text" if !args.trim().is_empty() => session = Some(set_text(args)),
+            command @ ("text" | "stats" | "top" | "explain" | "compare" | "period" | "freq" | "strip" | "transform"
+            | "history" | "undo" | "reset") => {
+                let Some(session) = session.as_mut() else {
+                    println!("No ciphertext yet. Paste one first.");
+                    continue;
+                };
+                if let Err(e) = execute(session, command, args, calibration, catalog) {
+                    println!("{} {}", "Error:".red(), e);
+                }
+            }
+            _ if is_pasted_text(line) => session = Some(set_text(line)),
+            _ => println!(
+                "{} '{}'. Type `help` for the list of commands, or `text <ciphertext>` to set a short ciphertext.",
+                "Unknown command".red(),
+                command
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Whether a line that is not a command is a pasted ciphertext rather than a mistyped command
+///
+/// A ciphertext is either a word longer than any command or a line of several words, so that
+/// e.g. `tpo 5` does not replace the text.
+fn is_pasted_text(line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    words.first().is_some_and(|w| w.chars().count() > MAX_COMMAND_LENGTH) || words.len() >= MIN_PASTED_WORDS
+}
+
+/// Starts a new session on a pasted ciphertext
+fn set_text(text: &str) -> Session {
+    let session = Session::new(preprocess(text));
+    println!("Ciphertext set ({} characters)", session.text().len());
+    session
+}
+
+/// Runs a command that needs a ciphertext
+fn execute(
+    session: &mut Session,
+    command: &str,
+    args: &str,
+    calibration: &Calibration,
+    catalog: &CipherCatalog,
+) -> Result<(), Box<dyn Error>> {
+    let args = args.trim();
+    match command {
+        "text" => println!("{}", session.text()),
+        "stats" => {
+            display_basic_stats(session.text());
+            display_statistics(&StatsReport::new(session.text()));
+        }
+        "top" => {
+            let number = if args.is_empty() { 5 } else { args.parse().map_err(|_| format!("Invalid number '{}'", args))? };
+            let candidates = identify_cipher::cipher_type_names();
+            let result =
+                identify_hierarchical(session.text(), &candidates, &Priors::uniform(), number, None, calibration, catalog);
+            display_identification(&result, number, None, calibration, catalog);
+        }
+        "explain" => {
+            let cipher = catalog.resolve(args)?;
+            display_explanation(&identify_cipher::get_cipher_stats(session.text()), cipher);
+        }
+        "compare" => {
+            let names: Vec<&str> = if args.contains(',') {
+                args.split(',').map(str::trim).collect()
+            } else {
+                args.split_whitespace().collect()
+            };
+            let [a, b] = names.as_slice() else {
+                return Err("Usage: compare <a>, <b>".into());
+            };
+            let stats = identify_cipher::get_cipher_stats(session.text());
+            let comparison = compare_ciphers(&stats, catalog.resolve(a)?, catalog.resolve(b)?, calibration)?;
+            display_comparison(&comparison);
+        }
+        "period" => {
+            let max_period = if args.is_empty() { 20 } else { args.parse().map_err(|_| format!("Invalid period '{}'", args))? };
+            display_periods(&get_periodic_ics(session.text(), max_period));
+        }
+        "freq" => display_frequencies(session.text()),
+        "strip" | "transform" => {
+            let transform = Transform::parse(command, args)?;
+            let before = session.text().len();
+            session.apply(transform);
+            println!("{} ({} -> {} characters)", session.history().last().map_or(String::new(), |t| t.to_string()), before, session.text().len());
+        }
+        "history" => {
+            if session.history().is_empty() {
+                println!("No transforms applied");
+            }
+            for (i, transform) in session.history().iter().enumerate() {
+                println!("{}. {}", i + 1, transform);
+            }
+        }
+        "undo" => match session.undo() {
+            Some(transform) => println!("Undid {}", transform),
+            None => println!("Nothing to undo"),
+        },
+        "reset" => {
+            session.reset();
+            println!("Back to the original text ({} characters)", session.text().len());
+        }
+        _ => unreachable!("commands are matched by run_repl"),
+    }
+    Ok(())
+}
+
+/// Prints the frequency of each symbol, most frequent first
+fn display_frequencies(text: &str) {
+    let mut counts: Vec<(char, usize)> = Vec::new();
+    for c in text.chars() {
+        match counts.iter_mut().find(|(symbol, _)| *symbol == c) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((c, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Symbol").style_spec("Fc"),
+        Cell::new("Count").style_spec("Fb"),
+        Cell::new("Share").style_spec("Fb"),
+    ]));
+    for (symbol, count) in counts {
+        table.add_row(Row::new(vec![
+            Cell::new(&symbol.to_string()),
+            Cell::new(&count.to_string()),
+            Cell::new(&format!("{:.1}%", count as f64 / text.len() as f64 * 100.0)),
+        ]));
+    }
+
+    println!("\n{}", "Symbol frequencies".bold());
+    print_table(&table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transforms_undo_in_order() {
+        let mut session = Session::new("HELLO42".to_string());
+        session.apply(Transform::parse("strip", "digits").unwrap());
+        session.apply(Transform::parse("transform", "shift -1").unwrap());
+        session.apply(Transform::Atbash);
+        assert_eq!(session.text(), "TWPPM");
+        assert_eq!(session.history()[1].to_string(), "transform shift 25");
+
+        assert_eq!(session.undo(), Some(Transform::Atbash));
+        assert_eq!(session.text(), "GDKKN");
+        session.reset();
+        assert_eq!(session.text(), "HELLO42");
+        assert!(session.undo().is_none());
+    }
+
+    #[test]
+    fn test_mistyped_commands_are_not_ciphertexts() {
+        for line in ["tpo 5", "explian playfair", "compre vigenere, beaufort", "stast"] {
+            assert!(!is_pasted_text(line), "{}", line);
+        }
+        for line in ["LXFOPVEFRNHRLXFOPVEFRNHR", "LXFOP VEFRN HRLXF OPVEF"] {
+            assert!(is_pasted_text(line), "{}", line);
+        }
+    }
+}
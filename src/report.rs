@@ -0,0 +1,422 @@
+//! Report Module
+//!
+//! This module collects the results of an analysis into a machine-readable report, so that
+//! pipelines can consume the output of the CLI as JSON, NDJSON or CSV instead of scraping the
+//! tables.
+//!
+//! The fields of the report are part of its schema. Any change that renames or removes a field
+//! must increase `SCHEMA_VERSION`; adding a field does not.
+
+use std::collections::{BTreeMap, HashSet};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::calibration::{Calibration, OodSignal, OutOfDistribution};
+use crate::constraints::Violation;
+use crate::hierarchy::{GroupProbability, HierarchicalIdentification};
+use crate::identify_cipher::{CipherScore, StatContribution};
+use crate::models::catalog::CipherCatalog;
+use crate::similarity::{DistanceMatrix, INDISTINGUISHABLE_DISTANCE};
+use crate::statistical_tests::all_stats::get_all_stats;
+use crate::statistical_tests::{binary_random, ioc, shannon_entropy};
+
+/// Version of the report schema
+///
+/// `--format json` always prints an array of reports, one per input, also for a single input.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Output format of the analysis and the catalog subcommands
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable table
+    Table,
+    /// JSON
+    Json,
+    /// JSON, one object per line
+    Ndjson,
+    /// CSV with a header row
+    Csv,
+}
+
+/// Basic statistics of a ciphertext, as shown in the "Basic stats" table
+#[derive(Debug, Clone, Serialize)]
+pub struct BasicStats {
+    /// Number of characters
+    pub length: usize,
+
+    /// Number of distinct characters
+    pub unique_characters: usize,
+
+    /// Letters of the alphabet that do not occur in the text
+    pub missing_letters: String,
+
+    /// Index of coincidence
+    pub ioc: f64,
+
+    /// Shannon entropy
+    pub shannon_entropy: f64,
+
+    /// Whether the text passes the binary random test
+    pub binary_random: bool,
+}
+
+impl BasicStats {
+    /// Column names of [`BasicStats::csv_fields`]
+    pub const CSV_COLUMNS: [&'static str; 6] =
+        ["length", "unique_characters", "missing_letters", "ioc", "shannon_entropy", "binary_random"];
+
+    /// Computes the basic statistics of a text
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::report::BasicStats;
+    ///
+    /// let stats = BasicStats::new("HELLOWORLD");
+    /// assert_eq!(stats.length, 10);
+    /// assert_eq!(stats.unique_characters, 7);
+    /// assert!(stats.missing_letters.starts_with("ABC"));
+    /// ```
+    pub fn new(text: &str) -> Self {
+        let present: HashSet<char> = text.chars().collect();
+        BasicStats {
+            length: text.len(),
+            unique_characters: present.len(),
+            missing_letters: ('A'..='Z').filter(|c| !present.contains(c)).collect(),
+            ioc: ioc::get_ioc(text),
+            shannon_entropy: shannon_entropy::get_shannon_entropy(text),
+            binary_random: binary_random::get_binary_random(text) == "Y",
+        }
+    }
+
+    /// Returns the fields in the order of [`BasicStats::CSV_COLUMNS`]
+    pub fn csv_fields(&self) -> [String; 6] {
+        [
+            self.length.to_string(),
+            self.unique_characters.to_string(),
+            self.missing_letters.clone(),
+            self.ioc.to_string(),
+            self.shannon_entropy.to_string(),
+            self.binary_random.to_string(),
+        ]
+    }
+}
+
+/// The statistics of one ciphertext, without identification
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsReport {
+    /// Version of the report schema, see [`SCHEMA_VERSION`]
+    pub schema_version: u32,
+
+    /// Where the ciphertext came from, e.g. a file and line number in batch mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Basic statistics of the text
+    pub basic_stats: BasicStats,
+
+    /// All statistics from `get_all_stats`, by name
+    pub statistics: BTreeMap<String, f64>,
+}
+
+impl StatsReport {
+    /// Computes the statistics of a text
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::report::StatsReport;
+    ///
+    /// let report = StatsReport::new("HELLOWORLD");
+    /// assert!(report.statistics.contains_key("IoC"));
+    /// assert_eq!(report.csv_header().len(), report.csv_record().len());
+    /// ```
+    pub fn new(text: &str) -> Self {
+        StatsReport {
+            schema_version: SCHEMA_VERSION,
+            source: None,
+            basic_stats: BasicStats::new(text),
+            statistics: get_all_stats(text).into_iter().collect(),
+        }
+    }
+
+    /// Returns the column names of the CSV form of the report
+    pub fn csv_header(&self) -> Vec<String> {
+        ["schema_version", "source"]
+            .iter()
+            .chain(&BasicStats::CSV_COLUMNS)
+            .map(|c| c.to_string())
+            .chain(self.statistics.keys().cloned())
+            .collect()
+    }
+
+    /// Returns the CSV form of the report as a single record
+    pub fn csv_record(&self) -> Vec<String> {
+        [self.schema_version.to_string(), self.source.clone().unwrap_or_default()]
+            .into_iter()
+            .chain(self.basic_stats.csv_fields())
+            .chain(self.statistics.values().map(|v| v.to_string()))
+            .collect()
+    }
+}
+
+/// A cipher type in the ranking of a report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportedCipher {
+    /// Position in the ranking, starting at 1
+    pub rank: usize,
+
+    /// Name of the cipher type
+    pub cipher: String,
+
+    /// Primary family of the cipher type
+    pub family: String,
+
+    /// Score of the cipher type (lower is better)
+    pub score: f64,
+
+    /// Calibrated probability, absent when the ranking comes from a trained model
+    pub probability: Option<f64>,
+}
+
+/// The machine-readable result of analyzing one ciphertext
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisReport {
+    /// Version of the report schema, see [`SCHEMA_VERSION`]
+    pub schema_version: u32,
+
+    /// Where the ciphertext came from, e.g. a file and line number in batch mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Basic statistics of the text
+    pub basic_stats: BasicStats,
+
+    /// All statistics from `get_all_stats`, by name
+    pub statistics: BTreeMap<String, f64>,
+
+    /// Whether no supported cipher fits the text
+    pub rejected: bool,
+
+    /// Median absolute z-score against the nearest cipher profile, plus a penalty per signal
+    pub ood_distance: f64,
+
+    /// The cipher type whose profile is nearest to the text
+    pub nearest_profile: String,
+
+    /// Signs that the text is not a ciphertext of a supported type
+    pub ood_signals: Vec<OodSignal>,
+
+    /// Probability per cipher family, most likely first
+    pub families: Vec<GroupProbability>,
+
+    /// The ranked cipher types, best first
+    pub ciphers: Vec<ReportedCipher>,
+
+    /// Groups of ranked ciphers that the statistics cannot separate
+    pub near_ties: Vec<Vec<String>>,
+
+    /// Constraints of the candidate ciphers that the text violates
+    pub violations: Vec<Violation>,
+
+    /// Contribution of each statistical test to the score of the cipher given with `--explain`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Vec<StatContribution>>,
+}
+
+impl AnalysisReport {
+    /// Creates the report of a hierarchical identification
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The analyzed ciphertext
+    /// * `result` - The identification of the text
+    /// * `catalog` - Metadata used to look up the family of each cipher
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::calibration::Calibration;
+    /// use cipher_identifier::hierarchy::identify_hierarchical;
+    /// use cipher_identifier::identify_cipher::cipher_type_names;
+    /// use cipher_identifier::models::catalog::CipherCatalog;
+    /// use cipher_identifier::priors::Priors;
+    /// use cipher_identifier::report::{AnalysisReport, SCHEMA_VERSION};
+    ///
+    /// let catalog = CipherCatalog::bundled();
+    /// let text = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+    /// let result = identify_hierarchical(text, &cipher_type_names(), &Priors::uniform(), 5, None, &Calibration::default(), &catalog);
+    /// let report = AnalysisReport::new(text, &result, &catalog);
+    /// assert_eq!(report.schema_version, SCHEMA_VERSION);
+    /// assert_eq!(report.ciphers.len(), 5);
+    /// ```
+    pub fn new(text: &str, result: &HierarchicalIdentification, catalog: &CipherCatalog) -> Self {
+        let ciphers = &result.ciphers;
+        let scores: Vec<CipherScore> = ciphers.candidates.iter().map(|c| (c.cipher.clone(), c.score)).collect();
+        let probabilities: Vec<f64> = ciphers.candidates.iter().map(|c| c.probability).collect();
+
+        let mut report = Self::from_ranking(text, &scores, Some(&probabilities), result.violations.clone(), catalog);
+        report.rejected = ciphers.rejected;
+        report.ood_distance = ciphers.ood_distance;
+        report.nearest_profile = ciphers.nearest_profile.clone();
+        report.ood_signals = ciphers.ood_signals.clone();
+        report.families = result.families.clone();
+        report.near_ties = DistanceMatrix::new(&scores.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>())
+            .near_ties(&scores, INDISTINGUISHABLE_DISTANCE);
+        report
+    }
+
+    /// Creates the report of a ranking that has no calibrated probabilities, e.g. from a trained model
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The analyzed ciphertext
+    /// * `scores` - The ranked (cipher_type, score) pairs
+    /// * `violations` - Constraints of the candidate ciphers that the text violates
+    /// * `calibration` - The calibration whose reject threshold is applied
+    /// * `catalog` - Metadata used to look up the family of each cipher
+    pub fn from_scores(
+        text: &str,
+        scores: &[CipherScore],
+        violations: Vec<Violation>,
+        calibration: &Calibration,
+        catalog: &CipherCatalog,
+    ) -> Self {
+        let mut report = Self::from_ranking(text, scores, None, violations, catalog);
+        report.rejected = OutOfDistribution::new(text, &crate::identify_cipher::get_cipher_stats(text))
+            .rejected(calibration.reject_threshold);
+        report
+    }
+
+    /// Fills in the fields shared by both kinds of rankings
+    fn from_ranking(
+        text: &str,
+        scores: &[CipherScore],
+        probabilities: Option<&[f64]>,
+        violations: Vec<Violation>,
+        catalog: &CipherCatalog,
+    ) -> Self {
+        let statistics: BTreeMap<String, f64> = get_all_stats(text).into_iter().collect();
+        let stats: Vec<f64> = crate::identify_cipher::STAT_NAMES.iter().map(|name| statistics[*name]).collect();
+        let ood = OutOfDistribution::new(text, &stats);
+
+        let ciphers = scores
+            .iter()
+            .enumerate()
+            .map(|(i, (cipher, score))| ReportedCipher {
+                rank: i + 1,
+                cipher: cipher.clone(),
+                family: catalog.primary_type(cipher),
+                score: *score,
+                probability: probabilities.map(|p| p[i]),
+            })
+            .collect();
+
+        AnalysisReport {
+            schema_version: SCHEMA_VERSION,
+            source: None,
+            basic_stats: BasicStats::new(text),
+            statistics,
+            rejected: false,
+            ood_distance: ood.distance,
+            nearest_profile: ood.nearest_profile,
+            ood_signals: ood.signals,
+            families: Vec::new(),
+            ciphers,
+            near_ties: Vec::new(),
+            violations,
+            explanation: None,
+        }
+    }
+
+    /// Returns the column names of the CSV form of the report
+    ///
+    /// There is one column per statistic, in the order of [`AnalysisReport::statistics`].
+    pub fn csv_header(&self) -> Vec<String> {
+        let columns = [
+            "schema_version",
+            "source",
+            "rank",
+            "cipher",
+            "family",
+            "score",
+            "probability",
+            "rejected",
+        ];
+        columns
+            .iter()
+            .chain(&BasicStats::CSV_COLUMNS)
+            .map(|c| c.to_string())
+            .chain(self.statistics.keys().cloned())
+            .collect()
+    }
+
+    /// Returns the CSV form of the report: one record per ranked cipher, repeating the text statistics
+    ///
+    /// A report without ranked ciphers still gives one record, with empty rank, cipher, family,
+    /// score and probability columns, so that every input appears in the output.
+    pub fn csv_records(&self) -> Vec<Vec<String>> {
+        let ranked: Vec<[String; 5]> = self
+            .ciphers
+            .iter()
+            .map(|cipher| {
+                [
+                    cipher.rank.to_string(),
+                    cipher.cipher.clone(),
+                    cipher.family.clone(),
+                    cipher.score.to_string(),
+                    cipher.probability.map_or(String::new(), |p| p.to_string()),
+                ]
+            })
+            .collect();
+        let ranked = if ranked.is_empty() { vec![Default::default()] } else { ranked };
+
+        ranked
+            .into_iter()
+            .map(|cipher_fields| {
+                [self.schema_version.to_string(), self.source.clone().unwrap_or_default()]
+                    .into_iter()
+                    .chain(cipher_fields)
+                    .chain([self.rejected.to_string()])
+                    .chain(self.basic_stats.csv_fields())
+                    .chain(self.statistics.values().map(|v| v.to_string()))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_records_match_header() {
+        let catalog = CipherCatalog::bundled();
+        let scores = vec![("Vigenere".to_string(), 5.0), ("columnar".to_string(), 7.5)];
+        let report = AnalysisReport::from_scores("HELLOWORLD", &scores, Vec::new(), &Calibration::default(), &catalog);
+
+        let header = report.csv_header();
+        let records = report.csv_records();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.len() == header.len()));
+        assert_eq!(records[1][header.iter().position(|c| c == "family").unwrap()], "transposition");
+        assert_eq!(records[0][header.iter().position(|c| c == "probability").unwrap()], "");
+    }
+
+    #[test]
+    fn test_csv_records_without_ranked_ciphers() {
+        let catalog = CipherCatalog::bundled();
+        let report = AnalysisReport::from_scores("HELLOWORLD", &[], Vec::new(), &Calibration::default(), &catalog);
+
+        let header = report.csv_header();
+        let records = report.csv_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].len(), header.len());
+        for column in ["rank", "cipher", "family", "score", "probability"] {
+            assert_eq!(records[0][header.iter().position(|c| c == column).unwrap()], "");
+        }
+        assert_eq!(records[0][header.iter().position(|c| c == "schema_version").unwrap()], "1");
+    }
+}
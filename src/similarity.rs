@@ -0,0 +1,229 @@
+//! Similarity Module
+//!
+//! This module measures how far apart the statistical profiles of the cipher types are, using
+//! the mean and standard deviation tables of `identify_cipher`, to tell which ciphers these
+//! statistics cannot separate.
+//!
+//! The distance between two profiles is the score a text matching one profile exactly would
+//! get against the other, averaged over both directions. It is in the same units as the scores
+//! of `identify_cipher::get_cipher`, so a score gap between two results can be compared to it.
+
+use serde::Serialize;
+
+use crate::identify_cipher::{self, CIPHER_TYPES, CipherScore};
+
+/// Profiles closer than this are considered statistically indistinguishable
+pub const INDISTINGUISHABLE_DISTANCE: f64 = 1.0;
+
+/// Calculates the distance between the profiles of two cipher types
+///
+/// # Arguments
+///
+/// * `a` - The first cipher type
+/// * `b` - The second cipher type
+///
+/// # Returns
+///
+/// The distance in score units, or `None` if either cipher type has no statistics tables
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::similarity::profile_separation;
+///
+/// let close = profile_separation("Vigenere", "Variant").unwrap();
+/// let far = profile_separation("Vigenere", "columnar").unwrap();
+/// assert!(close < far);
+/// assert_eq!(profile_separation("Vigenere", "Vigenere"), Some(0.0));
+/// ```
+pub fn profile_separation(a: &str, b: &str) -> Option<f64> {
+    let profile_a = identify_cipher::get_profile(a)?;
+    let profile_b = identify_cipher::get_profile(b)?;
+
+    let distance = profile_a
+        .iter()
+        .zip(&profile_b)
+        .map(|((mean_a, std_a), (mean_b, std_b))| {
+            let difference = (mean_a - mean_b).abs();
+            (difference / std_a + difference / std_b) / 2.0
+        })
+        .sum();
+    Some(distance)
+}
+
+/// A cipher whose profile is close to that of another
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Sibling {
+    /// The cipher type
+    pub cipher: String,
+
+    /// Distance between the two profiles, in score units
+    pub distance: f64,
+}
+
+/// Lists the supported ciphers whose profiles are closer to that of `cipher` than `threshold`
+///
+/// # Arguments
+///
+/// * `cipher` - The cipher type to find siblings of
+/// * `threshold` - The largest distance, usually [`INDISTINGUISHABLE_DISTANCE`]
+///
+/// # Returns
+///
+/// The siblings, closest first; empty if the cipher type has no statistics tables
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::similarity::{siblings, INDISTINGUISHABLE_DISTANCE};
+///
+/// let siblings = siblings("Vigenere", INDISTINGUISHABLE_DISTANCE);
+/// assert!(siblings.iter().any(|s| s.cipher == "Variant"));
+/// ```
+pub fn siblings(cipher: &str, threshold: f64) -> Vec<Sibling> {
+    let mut siblings: Vec<Sibling> = CIPHER_TYPES
+        .iter()
+        .filter(|other| **other != cipher)
+        .filter_map(|other| {
+            let distance = profile_separation(cipher, other)?;
+            (distance < threshold).then(|| Sibling { cipher: other.to_string(), distance })
+        })
+        .collect();
+    siblings.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(std::cmp::Ordering::Equal));
+    siblings
+}
+
+/// The pairwise distances between the profiles of a set of cipher types
+#[derive(Debug, Clone, Serialize)]
+pub struct DistanceMatrix {
+    /// The cipher types, in the order of the rows and columns
+    pub ciphers: Vec<String>,
+
+    /// The distance between each pair of cipher types
+    pub distances: Vec<Vec<f64>>,
+}
+
+impl DistanceMatrix {
+    /// Computes the distance matrix of the given cipher types
+    ///
+    /// # Arguments
+    ///
+    /// * `cipher_types` - The cipher types to compare; those without statistics tables are skipped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::identify_cipher::cipher_type_names;
+    /// use cipher_identifier::similarity::DistanceMatrix;
+    ///
+    /// let matrix = DistanceMatrix::new(&cipher_type_names());
+    /// assert_eq!(matrix.ciphers.len(), 58);
+    /// assert_eq!(matrix.distance("Vigenere", "Beaufort"), matrix.distance("Beaufort", "Vigenere"));
+    /// ```
+    pub fn new(cipher_types: &[String]) -> Self {
+        let ciphers: Vec<String> = cipher_types
+            .iter()
+            .filter(|c| identify_cipher::get_profile(c).is_some())
+            .cloned()
+            .collect();
+        let distances = ciphers
+            .iter()
+            .map(|a| ciphers.iter().map(|b| profile_separation(a, b).unwrap_or(f64::INFINITY)).collect())
+            .collect();
+        DistanceMatrix { ciphers, distances }
+    }
+
+    /// Returns the distance between two cipher types, or `None` if either is not in the matrix
+    pub fn distance(&self, a: &str, b: &str) -> Option<f64> {
+        let i = self.ciphers.iter().position(|c| c == a)?;
+        let j = self.ciphers.iter().position(|c| c == b)?;
+        Some(self.distances[i][j])
+    }
+
+    /// Groups ranked results that the statistics cannot separate
+    ///
+    /// Two results are tied if both their scores and their profiles are closer than `threshold`;
+    /// groups are formed from chains of ties.
+    ///
+    /// # Arguments
+    ///
+    /// * `scores` - The ranked (cipher_type, score) pairs
+    /// * `threshold` - The largest score gap and profile distance, usually [`INDISTINGUISHABLE_DISTANCE`]
+    ///
+    /// # Returns
+    ///
+    /// The groups of two or more tied results, each in ranking order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::identify_cipher::cipher_type_names;
+    /// use cipher_identifier::similarity::{DistanceMatrix, INDISTINGUISHABLE_DISTANCE};
+    ///
+    /// let matrix = DistanceMatrix::new(&cipher_type_names());
+    /// let scores = vec![("Vigenere".to_string(), 5.0), ("Variant".to_string(), 5.2), ("columnar".to_string(), 5.4)];
+    /// let groups = matrix.near_ties(&scores, INDISTINGUISHABLE_DISTANCE);
+    /// assert_eq!(groups, vec![vec!["Vigenere".to_string(), "Variant".to_string()]]);
+    /// ```
+    pub fn near_ties(&self, scores: &[CipherScore], threshold: f64) -> Vec<Vec<String>> {
+        // Union-find over the positions in the ranking
+        let mut parent: Vec<usize> = (0..scores.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for i in 0..scores.len() {
+            for j in i + 1..scores.len() {
+                let tied = (scores[i].1 - scores[j].1).abs() < threshold
+                    && self.distance(&scores[i].0, &scores[j].0).is_some_and(|d| d < threshold);
+                if tied {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        let mut roots: Vec<usize> = Vec::new();
+        for (i, (cipher, _)) in scores.iter().enumerate() {
+            let r = root(&mut parent, i);
+            match roots.iter().position(|&x| x == r) {
+                Some(k) => groups[k].push(cipher.clone()),
+                None => {
+                    roots.push(r);
+                    groups.push(vec![cipher.clone()]);
+                }
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siblings_are_symmetric_and_sorted() {
+        let siblings = siblings("Vigenere", INDISTINGUISHABLE_DISTANCE);
+
+        assert!(siblings.iter().any(|s| s.cipher == "Variant"));
+        assert!(siblings.iter().all(|s| s.cipher != "Vigenere"));
+        assert!(siblings.windows(2).all(|w| w[0].distance <= w[1].distance));
+        for sibling in &siblings {
+            assert!(super::siblings(&sibling.cipher, INDISTINGUISHABLE_DISTANCE).iter().any(|s| s.cipher == "Vigenere"));
+        }
+    }
+
+    #[test]
+    fn test_near_ties_need_close_scores() {
+        let matrix = DistanceMatrix::new(&identify_cipher::cipher_type_names());
+        let scores = vec![("Vigenere".to_string(), 5.0), ("Variant".to_string(), 9.0)];
+        assert!(matrix.near_ties(&scores, INDISTINGUISHABLE_DISTANCE).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+//! Kasiski Examination
+//!
+//! This module implements the Kasiski examination, which counts how many distances between
+//! repeated trigrams are divisible by each candidate key length. In a periodic polyalphabetic
+//! cipher, repeats of the same plaintext at the same key position are a multiple of the
+//! period apart, so the period and its factors stand out.
+
+use crate::statistical_tests::utils::convert_string;
+use std::collections::HashMap;
+
+/// Counts the distances between repeated trigrams that each factor divides
+///
+/// # Arguments
+///
+/// * `text` - The input text to analyze
+/// * `max_factor` - The largest factor to count
+///
+/// # Returns
+///
+/// The (factor, count) pairs for the factors 2 to `max_factor`
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::statistical_tests::kasiski::get_kasiski_factors;
+///
+/// let text = "LXFOPVEFRNHRLXFOPVEFRNHR";
+/// let factors = get_kasiski_factors(text, 12);
+/// assert_eq!(factors.len(), 11);
+/// assert!(factors.iter().find(|(f, _)| *f == 12).unwrap().1 > 0);
+/// ```
+pub fn get_kasiski_factors(text: &str, max_factor: usize) -> Vec<(usize, usize)> {
+    let data = convert_string(text);
+
+    // Record where each trigram occurs
+    let mut positions: HashMap<&[usize], Vec<usize>> = HashMap::new();
+    for i in 0..data.len().saturating_sub(2) {
+        positions.entry(&data[i..i + 3]).or_default().push(i);
+    }
+
+    // Distances between consecutive occurrences of the same trigram
+    let distances: Vec<usize> = positions
+        .values()
+        .flat_map(|occurrences| occurrences.windows(2).map(|pair| pair[1] - pair[0]))
+        .collect();
+
+    (2..=max_factor)
+        .map(|factor| (factor, distances.iter().filter(|d| d.is_multiple_of(factor)).count()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kasiski_no_repeats() {
+        let factors = get_kasiski_factors("ABCDEFGHIJKLMNOPQRSTUVWXYZ", 10);
+        assert!(factors.iter().all(|(_, count)| *count == 0));
+    }
+
+    #[test]
+    fn test_kasiski_period_factors() {
+        // Each trigram repeats 6 symbols later, so 2, 3 and 6 divide every distance
+        let factors = get_kasiski_factors("ABCDEFABCDEFABCDEF", 6);
+        let counts: HashMap<usize, usize> = factors.into_iter().collect();
+        assert_eq!(counts[&6], counts[&2]);
+        assert_eq!(counts[&6], counts[&3]);
+        assert_eq!(counts[&4], 0);
+    }
+}
@@ -7,6 +7,13 @@
 use crate::statistical_tests::utils::convert_string;
 use std::collections::HashMap;
 
+/// English letter frequencies (A-Z)
+pub const ENGLISH_FREQUENCIES: [f64; 26] = [
+    0.082, 0.015, 0.028, 0.043, 0.127, 0.022, 0.020, 0.061, 0.070, 0.002,
+    0.008, 0.040, 0.024, 0.067, 0.075, 0.019, 0.001, 0.060, 0.063, 0.091,
+    0.028, 0.010, 0.023, 0.001, 0.020, 0.001
+];
+
 /// Calculates the Letter Distribution Index (LDI) for the given text
 ///
 /// The Letter Distribution Index measures how closely the letter distribution
@@ -37,13 +44,6 @@ pub fn get_ldi(text: &str) -> f64 {
         return 0.0;
     }
     
-    // English letter frequencies (A-Z)
-    let english_freqs = [
-        0.082, 0.015, 0.028, 0.043, 0.127, 0.022, 0.020, 0.061, 0.070, 0.002,
-        0.008, 0.040, 0.024, 0.067, 0.075, 0.019, 0.001, 0.060, 0.063, 0.091,
-        0.028, 0.010, 0.023, 0.001, 0.020, 0.001
-    ];
-    
     // Count letter frequencies in the text
     let mut letter_counts = HashMap::new();
     let mut total_letters = 0;
@@ -62,9 +62,9 @@ pub fn get_ldi(text: &str) -> f64 {
     // Calculate chi-squared statistic
     let mut chi_squared = 0.0;
     
-    for i in 0..26 {
+    for (i, &freq) in ENGLISH_FREQUENCIES.iter().enumerate() {
         let observed = *letter_counts.get(&i).unwrap_or(&0) as f64;
-        let expected = english_freqs[i] * total_letters as f64;
+        let expected = freq * total_letters as f64;
         
         if expected > 0.0 {
             let diff = observed - expected;
@@ -49,6 +49,36 @@ pub fn get_max_periodic_ic(text: &str) -> f64 {
     max_ic
 }
 
+/// Calculates the Index of Coincidence of the text split into columns, for each period size
+///
+/// Periods at which the IoC rises towards that of plain language are likely key lengths of a
+/// periodic polyalphabetic cipher.
+///
+/// # Arguments
+///
+/// * `text` - The input text to analyze
+/// * `max_period` - The largest period to try; periods above half the text length are skipped
+///
+/// # Returns
+///
+/// The (period, IoC) pairs, starting at period 1
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::statistical_tests::mic::{get_max_periodic_ic, get_periodic_ics};
+///
+/// let text = "LXFOPVEFRNHRLXFOPVEFRNHR";
+/// let ics = get_periodic_ics(text, 10);
+/// assert_eq!(ics.len(), 10);
+/// assert_eq!(ics.iter().map(|(_, ic)| *ic).fold(0.0, f64::max), get_max_periodic_ic(text));
+/// ```
+pub fn get_periodic_ics(text: &str, max_period: usize) -> Vec<(usize, f64)> {
+    let data = convert_string(text);
+    let max_period = std::cmp::min(max_period, data.len() / 2);
+    (1..=max_period).map(|period| (period, calculate_periodic_ic(&data, period))).collect()
+}
+
 /// Calculates the Index of Coincidence for a specific period size
 ///
 /// # Arguments
@@ -12,6 +12,7 @@ pub mod lr;
 pub mod rod;
 pub mod ldi;
 pub mod sdd;
+pub mod kasiski;
 pub mod binary_random;
 pub mod shannon_entropy;
 pub mod all_stats;
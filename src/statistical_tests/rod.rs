@@ -47,12 +47,12 @@ pub fn get_rod(text: &str) -> f64 {
     let mut count = 0;
     
     for (i, &c) in data.iter().enumerate() {
-        if let Some(&first_pos) = first_occurrences.get(&c) {
-            if i > first_pos {
-                // This is a repeat of a character
-                total_distance += (i - first_pos) as f64;
-                count += 1;
-            }
+        if let Some(&first_pos) = first_occurrences.get(&c)
+            && i > first_pos
+        {
+            // This is a repeat of a character
+            total_distance += (i - first_pos) as f64;
+            count += 1;
         }
     }
     
@@ -0,0 +1,165 @@
+//! Watch Module
+//!
+//! This module supports re-analyzing a ciphertext file whenever it changes: it polls the file
+//! for a new ciphertext, and compares consecutive rankings to show which candidates moved.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::input::preprocess;
+
+/// How often the watched file is checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls a file for a changed ciphertext
+pub struct FilePoller {
+    path: PathBuf,
+    last: Option<String>,
+}
+
+impl FilePoller {
+    /// Creates a poller for the file at `path`; the first poll always returns its ciphertext
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FilePoller { path: path.into(), last: None }
+    }
+
+    /// Reads the ciphertext of the file, which the next poll then treats as unchanged
+    ///
+    /// Unlike [`FilePoller::poll`], this fails if the file cannot be read, so that a file that
+    /// is missing when watching starts is reported instead of being waited for forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::watch::FilePoller;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let mut poller = FilePoller::new(dir.path().join("missing.txt"));
+    /// assert!(poller.read().is_err());
+    ///
+    /// std::fs::write(dir.path().join("missing.txt"), "lxfop").unwrap();
+    /// assert_eq!(poller.read().unwrap(), "LXFOP");
+    /// assert_eq!(poller.poll(), None);
+    /// ```
+    pub fn read(&mut self) -> io::Result<String> {
+        let text = preprocess(&fs::read_to_string(&self.path)?);
+        self.last = Some(text.clone());
+        Ok(text)
+    }
+
+    /// Returns the ciphertext of the file if it changed since the last poll
+    ///
+    /// Changes that only affect whitespace or case are ignored, since they do not change the
+    /// analysis. A file that cannot be read, for example while an editor replaces it, counts as
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cipher_identifier::watch::FilePoller;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("cipher.txt");
+    /// std::fs::write(&path, "lxfop vefrn").unwrap();
+    ///
+    /// let mut poller = FilePoller::new(&path);
+    /// assert_eq!(poller.poll().as_deref(), Some("LXFOPVEFRN"));
+    /// assert_eq!(poller.poll(), None);
+    /// ```
+    pub fn poll(&mut self) -> Option<String> {
+        let text = preprocess(&fs::read_to_string(&self.path).ok()?);
+        if self.last.as_ref() == Some(&text) {
+            return None;
+        }
+        self.last = Some(text.clone());
+        Some(text)
+    }
+}
+
+/// How the rank of a cipher changed between two runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankChange {
+    /// The cipher was not ranked in the previous run
+    New,
+
+    /// The cipher moved up by this many places
+    Up(usize),
+
+    /// The cipher moved down by this many places
+    Down(usize),
+
+    /// The cipher kept its place
+    Unchanged,
+
+    /// The cipher is no longer ranked
+    Dropped,
+}
+
+/// Compares two rankings
+///
+/// # Arguments
+///
+/// * `previous` - The ciphers of the previous run, best first
+/// * `current` - The ciphers of the current run, best first
+///
+/// # Returns
+///
+/// The change of each cipher of the current ranking, in its order, followed by the ciphers
+/// that dropped out
+///
+/// # Examples
+///
+/// ```
+/// use cipher_identifier::watch::{RankChange, rank_changes};
+///
+/// let previous = ["playfair", "Vigenere", "bifid"].map(String::from);
+/// let current = ["Vigenere", "playfair", "porta"].map(String::from);
+/// let changes = rank_changes(&previous, &current);
+/// assert_eq!(changes[0], ("Vigenere".to_string(), RankChange::Up(1)));
+/// assert_eq!(changes[2], ("porta".to_string(), RankChange::New));
+/// assert_eq!(changes[3], ("bifid".to_string(), RankChange::Dropped));
+/// ```
+pub fn rank_changes(previous: &[String], current: &[String]) -> Vec<(String, RankChange)> {
+    let mut changes: Vec<(String, RankChange)> = current
+        .iter()
+        .enumerate()
+        .map(|(rank, cipher)| {
+            let change = match previous.iter().position(|c| c == cipher) {
+                None => RankChange::New,
+                Some(old) if old > rank => RankChange::Up(old - rank),
+                Some(old) if old < rank => RankChange::Down(rank - old),
+                Some(_) => RankChange::Unchanged,
+            };
+            (cipher.clone(), change)
+        })
+        .collect();
+    changes.extend(
+        previous
+            .iter()
+            .filter(|cipher| !current.contains(cipher))
+            .map(|cipher| (cipher.clone(), RankChange::Dropped)),
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poller_ignores_whitespace_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cipher.txt");
+        let mut poller = FilePoller::new(&path);
+        assert_eq!(poller.poll(), None);
+
+        fs::write(&path, "ABC DEF").unwrap();
+        assert_eq!(poller.poll().as_deref(), Some("ABCDEF"));
+        fs::write(&path, "abc\ndef\n").unwrap();
+        assert_eq!(poller.poll(), None);
+        fs::write(&path, "ABCDEX").unwrap();
+        assert_eq!(poller.poll().as_deref(), Some("ABCDEX"));
+    }
+}